@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::error::AuctionError;
+
 #[account]
 pub struct OpenAuction {
     pub owner: Pubkey,
@@ -10,24 +12,130 @@ pub struct OpenAuction {
     pub end_time: u64,
     pub cancelled: bool,
 
+    // soft close: a bid within extension_window seconds of end_time pushes it back by extension_length
+    pub extension_window: u64,
+    pub extension_length: u64,
+    // 0 allows unlimited extensions
+    pub max_extensions: u64,
+    pub extensions: u64,
+
+    // candle mode: the real close is drawn from the last candle_window seconds after end_time,
+    // so every change of lead inside the window is logged with its timestamp
+    pub candle_window: u64,
+    pub candle_log_cap: u64,
+    pub candle_log: Vec<CandleBid>,
+    // committed after end_time so nobody knows the hash that draws the close
+    pub candle_draw_slot: u64,
+    pub candle_cutoff: u64,
+    pub candle_resolved: bool,
+
     pub title: String,
 
     pub bidder_cap: u64,
     pub bidders: Vec<Pubkey>,
     pub bids: Vec<u64>,
+    // lower wins between equal bids, set whenever a bid changes, see tie_break_key
+    pub tie_keys: Vec<u64>,
+    pub tie_break: TieBreak,
+    // a random tie-break orders equal bids from this slot's hash, committed after end_time
+    pub tie_draw_slot: u64,
+    pub ties_drawn: bool,
 
     pub highest_bidder: Pubkey,
     pub highest_bid: u64,
 
     pub bid_floor: u64,
     pub min_bid_increment: u64,
+    // with proxy bidding, bids holds each bidder's escrowed maximum
+    // and highest_bid is the visible price the winner pays
+    pub proxy_bidding: bool,
+    pub winning_bid_withdrawn: bool,
+
+    // 0 disables buy-now, which also closes once highest_bid passes buy_now_cutoff_bps of the price
+    pub buy_now_price: u64,
+    pub buy_now_cutoff_bps: u64,
+    pub item_withdrawn: bool,
+
+    // all zeroes means no hidden reserve
+    pub reserve_hash: [u8; 32],
+    // seconds after end_time the seller has to reveal the reserve
+    pub reserve_reveal_window: u64,
+    pub reserve_price: u64,
+    pub reserve_revealed: bool,
+
+    // extra mints sold together with the main one
+    pub bundle: Vec<BundleItem>,
+
+    // token_amount is sold as this many equal units to the highest bidders, each paying their own bid
+    pub units: u64,
+    // multi-unit winners who have withdrawn their unit
+    pub unit_claims: Vec<Pubkey>,
+    pub unsold_reclaimed: bool,
 
     pub bump: u8,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct CandleBid {
+    pub bidder: Pubkey,
+    pub bid: u64,
+    pub time: u64,
+}
+
+#[derive(PartialEq)]
+pub enum ReserveStatus {
+    Met,
+    NotMet,
+    Pending,
+}
+
+// optional open auction features, a config left as None is off
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OpenAuctionOptions {
+    pub soft_close: Option<SoftCloseConfig>,
+    pub buy_now: Option<BuyNowConfig>,
+    pub reserve: Option<ReserveConfig>,
+    pub proxy_bidding: bool,
+    pub candle: Option<CandleConfig>,
+    pub bundle_amounts: Vec<u64>,
+    pub units: u64,
+    pub tie_break: TieBreak,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct SoftCloseConfig {
+    pub window: u64,
+    pub length: u64,
+    pub max_extensions: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct BuyNowConfig {
+    pub price: u64,
+    pub cutoff_bps: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ReserveConfig {
+    pub hash: [u8; 32],
+    pub reveal_window: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct CandleConfig {
+    pub window: u64,
+    pub log_cap: u64,
+}
+
 #[account]
 pub struct SealedAuction {
     pub first_price: bool,
+    // how sealed_bids are committed, see compute_bid_hash and compute_bid_hash_v2
+    pub commitment_version: u8,
+    pub tie_break: TieBreak,
+    // a random tie-break orders equal top bids from this slot's hash, committed after reveal_period
+    pub tie_draw_slot: u64,
+    pub ties_drawn: bool,
 
     pub owner: Pubkey,
     pub mint: Pubkey,
@@ -37,6 +145,8 @@ pub struct SealedAuction {
     pub end_time: u64,
     pub reveal_period: u64,
     pub cancelled: bool,
+    // only ever written by transition
+    pub phase: Phase,
 
     pub title: String,
 
@@ -44,13 +154,382 @@ pub struct SealedAuction {
     pub bidders: Vec<Pubkey>,
     pub sealed_bids: Vec<[u8; 32]>,
     pub fake_bids: Vec<u64>,
+    pub revealed: Vec<bool>,
+    pub commit_times: Vec<u64>,
 
+    // every valid reveal ranked by bid, highest_bidder and highest_bid mirror the top entry
+    pub reveal_book: Vec<RevealedBid>,
     pub highest_bidder: Pubkey,
     pub highest_bid: u64,
-    pub second_highest_bid: u64,
 
     pub bid_floor: u64,
     pub winning_bid_withdrawn: bool,
+    pub item_withdrawn: bool,
+
+    pub bundle: Vec<BundleItem>,
+
+    // whether update_sealed_bid can lower a deposit, otherwise it can only top up
+    pub allow_deposit_decrease: bool,
+
+    // bids can also be sent encrypted to this key so a keeper can reveal for every bidder,
+    // the default pubkey turns this off
+    pub auctioneer: Pubkey,
+    pub ciphertexts: Vec<Vec<u8>>,
+
+    // deposits must be one of these ascending amounts so they say little about the bid,
+    // empty accepts any deposit
+    pub deposit_tiers: Vec<u64>,
+
+    // bidders who never reveal forfeit penalty_bps of their fake bid, or a fixed penalty_bond if set
+    pub penalty_bps: u64,
+    pub penalty_bond: u64,
+    pub penalty_recipient: Pubkey,
+    pub forfeited: u64,
+
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum Phase {
+    Scheduled,
+    Bidding,
+    Revealing,
+    Settling,
+    Settled,
+    Cancelled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum TieBreak {
+    EarliestCommit,
+    EarliestReveal,
+    // drawn from a slot committed once bidding or revealing is over, see tie_break_key
+    Random,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct RevealedBid {
+    pub bidder: Pubkey,
+    pub bid: u64,
+    pub commit_time: u64,
+    pub reveal_time: u64,
+    // lower ranks first among equal bids
+    pub tie_key: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct BundleItem {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+// optional sealed auction features, a config left as None is off
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SealedAuctionOptions {
+    pub bundle_amounts: Vec<u64>,
+    pub commitment_version: u8,
+    pub penalty: Option<PenaltyConfig>,
+    pub deposits: Option<DepositConfig>,
+    pub auctioneer: Option<Pubkey>,
+    pub tie_break: TieBreak,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PenaltyConfig {
+    pub bps: u64,
+    pub bond: u64,
+    pub recipient: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct DepositConfig {
+    pub tiers: Vec<u64>,
+    pub allow_decrease: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum DecayCurve {
+    Linear,
+    Exponential,
+}
+
+#[account]
+pub struct DutchAuction {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub token_amount: u64,
+
+    pub start_time: u64,
+    pub end_time: u64,
+    pub cancelled: bool,
+
+    pub title: String,
+
+    pub start_price: u64,
+    pub bid_floor: u64,
+    pub decay_curve: DecayCurve,
+    // seconds for the price premium over the floor to halve, only used by exponential decay
+    pub half_life: u64,
+
+    pub buyer: Pubkey,
+    pub sale_price: u64,
+    pub sold: bool,
+
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq)]
+pub enum ClearingRule {
+    LowestWinning,
+    HighestLosing,
+    // no uniform price, each winner pays the value their units displace from the other bids
+    Vcg,
+}
+
+#[account]
+pub struct MultiUnitSealedAuction {
+    pub clearing_rule: ClearingRule,
+
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub token_amount: u64,
+    // token_amount is sold as this many equal units
+    pub units: u64,
+
+    pub start_time: u64,
+    pub end_time: u64,
+    pub reveal_period: u64,
+    pub cancelled: bool,
+
+    pub title: String,
+
+    pub bidder_cap: u64,
+    pub bidders: Vec<Pubkey>,
+    pub sealed_bids: Vec<[u8; 32]>,
+    pub fake_bids: Vec<u64>,
+    // revealed quantity and per-unit price, 0 until revealed
+    pub bid_units: Vec<u64>,
+    pub bid_prices: Vec<u64>,
+    pub allocations: Vec<u64>,
+    // what each bidder owes out of their fake bid, set at settlement
+    pub payments: Vec<u64>,
+
+    // per unit
+    pub bid_floor: u64,
+
+    pub settled: bool,
+    pub clearing_price: u64,
+    pub units_sold: u64,
+    pub proceeds_withdrawn: bool,
+    pub item_reclaimed: bool,
+
+    pub bump: u8,
+}
+
+// the owner is the buyer and suppliers compete to sell them token_amount of mint
+#[account]
+pub struct ReverseAuction {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub token_amount: u64,
+    pub budget: u64,
+
+    pub start_time: u64,
+    pub end_time: u64,
+    pub cancelled: bool,
+
+    pub title: String,
+
+    pub supplier_cap: u64,
+    pub suppliers: Vec<Pubkey>,
+    pub offers: Vec<u64>,
+
+    pub lowest_supplier: Pubkey,
+    pub lowest_offer: u64,
+
+    pub min_offer_decrement: u64,
+
+    pub item_withdrawn: bool,
+    pub payment_withdrawn: bool,
+    pub budget_reclaimed: bool,
+
+    pub bump: u8,
+}
+
+// the clock rises from bid_floor by price_increment every step_length seconds after start_time
+#[account]
+pub struct ClockAuction {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub token_amount: u64,
+
+    pub start_time: u64,
+    pub cancelled: bool,
+
+    pub title: String,
+
+    pub bid_floor: u64,
+    pub price_increment: u64,
+    pub step_length: u64,
+
+    pub bidder_cap: u64,
+    pub bidders: Vec<Pubkey>,
+    pub deposits: Vec<u64>,
+    // 0 while the bidder is still in
+    pub exit_prices: Vec<u64>,
+
+    pub settled: bool,
+    pub winner: Pubkey,
+    pub clearing_price: u64,
+
+    pub winning_bid_withdrawn: bool,
+    pub item_withdrawn: bool,
+
+    pub bump: u8,
+}
+
+#[account]
+pub struct Raffle {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub token_amount: u64,
+
+    pub start_time: u64,
+    pub end_time: u64,
+    pub cancelled: bool,
+
+    pub title: String,
+
+    pub ticket_price: u64,
+    pub ticket_cap: u64,
+    pub min_tickets: u64,
+    pub tickets_sold: u64,
+
+    pub buyer_cap: u64,
+    pub buyers: Vec<Pubkey>,
+    pub tickets: Vec<u64>,
+
+    // slot whose hash decides the draw, committed before it's produced
+    pub draw_slot: u64,
+    pub drawn: bool,
+    pub winner: Pubkey,
+
+    pub proceeds_withdrawn: bool,
+    pub item_withdrawn: bool,
+
+    pub bump: u8,
+}
+
+// each lot is one escrowed (mint, amount) pair and bids are on packages of lots
+#[account]
+pub struct PackageSealedAuction {
+    pub owner: Pubkey,
+
+    pub start_time: u64,
+    pub end_time: u64,
+    pub reveal_period: u64,
+    pub cancelled: bool,
+
+    pub title: String,
+
+    pub lots: Vec<BundleItem>,
+
+    pub bidder_cap: u64,
+    pub bidders: Vec<Pubkey>,
+    pub sealed_bids: Vec<[u8; 32]>,
+    pub fake_bids: Vec<u64>,
+    // bitmask of lot indices, 0 until revealed
+    pub packages: Vec<u8>,
+    pub bids: Vec<u64>,
+    pub winners: Vec<bool>,
+
+    pub bid_floor: u64,
+
+    pub settled: bool,
+    pub revenue: u64,
+    pub proceeds_withdrawn: bool,
+    pub item_reclaimed: bool,
+
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct CatalogBid {
+    // index into the auction's bidders
+    pub bidder_index: u32,
+    pub bid: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CatalogLot {
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub highest_bidder: Pubkey,
+    pub highest_bid: u64,
+    pub item_withdrawn: bool,
+    pub winning_bid_withdrawn: bool,
+    // escrow per bidder on this lot so escrow on one lot can't back a bid on another
+    pub bids: Vec<CatalogBid>,
+}
+
+// many independent open auction lots sharing one schedule
+#[account]
+pub struct CatalogAuction {
+    pub owner: Pubkey,
+
+    pub start_time: u64,
+    pub end_time: u64,
+    pub cancelled: bool,
+
+    pub title: String,
+
+    pub lot_cap: u64,
+    pub lots: Vec<CatalogLot>,
+
+    pub bidder_cap: u64,
+    pub bidders: Vec<Pubkey>,
+    pub lot_bidder_cap: u64,
+
+    pub bid_floor: u64,
+    pub min_bid_increment: u64,
+
+    pub bump: u8,
+}
+
+// back-to-back rounds that each mint token_amount of a mint whose authority is this pda
+#[account]
+pub struct SeriesAuction {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub token_amount: u64,
+
+    pub title: String,
+
+    // round duration, floor, and increment live in the series config account
+    pub config: Pubkey,
+
+    pub round: u64,
+    pub round_start: u64,
+    pub round_end: u64,
+    pub round_settled: bool,
+    // the round in progress still finishes but no new one starts
+    pub stopped: bool,
+
+    // only the leading bid is escrowed, the previous one is refunded when it's beaten
+    pub highest_bidder: Pubkey,
+    pub highest_bid: u64,
+
+    pub bump: u8,
+}
+
+// a series' round parameters, kept apart from the state of the round in progress
+#[account]
+pub struct SeriesConfig {
+    pub auction: Pubkey,
+
+    pub round_duration: u64,
+    pub bid_floor: u64,
+    pub min_bid_increment: u64,
 
     pub bump: u8,
 }
@@ -58,12 +537,28 @@ pub struct SealedAuction {
 pub const DISCRIMINATOR_LENGTH: usize = 8;
 pub const PUBLIC_KEY_LENGTH: usize = 32;
 pub const U64_LENGTH: usize = 8;
+pub const U32_LENGTH: usize = 4;
 pub const U8_LENGTH: usize = 1;
 pub const ENUM_LENGTH: usize = 1;
 pub const BOOL_LENGTH: usize = 1;
 pub const STRING_LENGTH_PREFIX: usize = 4;
 pub const MAX_TITLE_LENGTH: usize = 50 * 4;
 pub const VECTOR_LENGTH_PREFIX: usize = 4;
+pub const BPS_DENOMINATOR: u64 = 10000;
+// slots between committing a draw and the slot whose hash decides it
+pub const DRAW_DELAY: u64 = 10;
+// winner determination is a dp over every subset of lots for every package bid
+pub const MAX_LOTS: usize = 8;
+pub const MAX_PACKAGE_BIDS: u64 = 16;
+// vcg reruns the allocation once per winner
+pub const MAX_VCG_BIDDERS: u64 = 16;
+pub const COMMITMENT_V1: u8 = 1;
+pub const COMMITMENT_V2: u8 = 2;
+// room for an x25519 box of the bid and salt with its ephemeral key and nonce
+pub const MAX_CIPHERTEXT_LENGTH: usize = 128;
+pub const MAX_DEPOSIT_TIERS: usize = 8;
+// accounts created through a cpi, like anchor's init, can be at most 10 KiB
+pub const MAX_INIT_ACCOUNT_SIZE: usize = 10 * 1024;
 
 impl OpenAuction {
     pub const LEN: usize = DISCRIMINATOR_LENGTH
@@ -73,18 +568,154 @@ impl OpenAuction {
         + U64_LENGTH // start time
         + U64_LENGTH // end time
         + BOOL_LENGTH // cancelled
+        + U64_LENGTH // extension window
+        + U64_LENGTH // extension length
+        + U64_LENGTH // max extensions
+        + U64_LENGTH // extensions
+        + U64_LENGTH // candle window
+        + U64_LENGTH // candle log cap
+        + U64_LENGTH // candle draw slot
+        + U64_LENGTH // candle cutoff
+        + BOOL_LENGTH // candle resolved
         + STRING_LENGTH_PREFIX + MAX_TITLE_LENGTH // title
         + U64_LENGTH // bidder cap
+        + ENUM_LENGTH // tie break
+        + U64_LENGTH // tie draw slot
+        + BOOL_LENGTH // ties drawn
         + PUBLIC_KEY_LENGTH // highest bidder
         + U64_LENGTH // highest bid
         + U64_LENGTH // bid floor
         + U64_LENGTH // min bid increment
+        + BOOL_LENGTH // proxy bidding
+        + BOOL_LENGTH // winning bid withdrawn
+        + U64_LENGTH // buy now price
+        + U64_LENGTH // buy now cutoff bps
+        + BOOL_LENGTH // item withdrawn
+        + U8_LENGTH*32 // reserve hash
+        + U64_LENGTH // reserve reveal window
+        + U64_LENGTH // reserve price
+        + BOOL_LENGTH // reserve revealed
+        + U64_LENGTH // units
+        + BOOL_LENGTH // unsold reclaimed
         + U8_LENGTH; // bump
+
+    pub fn candle_window_start(&self) -> u64 {
+        self.end_time - self.candle_window
+    }
+
+    // the window splits into at most candle_log_cap buckets of whole seconds
+    pub fn candle_bucket_length(&self) -> u64 {
+        (self.candle_window + self.candle_log_cap - 1) / self.candle_log_cap
+    }
+
+    pub fn candle_bucket(&self, time: u64) -> u64 {
+        (time - self.candle_window_start()) / self.candle_bucket_length()
+    }
+
+    pub fn has_reserve(&self) -> bool {
+        self.reserve_hash != [0u8; 32]
+    }
+
+    // an unrevealed reserve is ignored once the reveal window has passed
+    pub fn reserve_status(&self, cur_time: u64) -> ReserveStatus {
+        if !self.has_reserve() {
+            ReserveStatus::Met
+        } else if self.reserve_revealed {
+            if self.highest_bid >= self.reserve_price {
+                ReserveStatus::Met
+            } else {
+                ReserveStatus::NotMet
+            }
+        } else if cur_time > self.end_time + self.reserve_reveal_window {
+            ReserveStatus::Met
+        } else {
+            ReserveStatus::Pending
+        }
+    }
+
+    pub fn unit_amount(&self) -> u64 {
+        self.token_amount / self.units
+    }
+
+    // indices of the top bids, ties go by tie key and then to whoever joined first
+    pub fn winning_indices(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.bids.len()).collect();
+        order.sort_by(|&a, &b| {
+            self.bids[b].cmp(&self.bids[a]).then(self.tie_keys[a].cmp(&self.tie_keys[b]))
+        });
+        order.truncate(self.units as usize);
+        order
+    }
+
+    // the bid a new bid has to beat by min_bid_increment, 0 while there are units nobody is winning
+    pub fn lowest_winning_bid(&self) -> u64 {
+        if self.units == 1 {
+            return self.highest_bid;
+        }
+        let winners = self.winning_indices();
+        if winners.len() < self.units as usize {
+            0
+        } else {
+            self.bids[winners[winners.len() - 1]]
+        }
+    }
+
+    pub fn is_winner(&self, bidder: &Pubkey) -> bool {
+        if self.units == 1 {
+            *bidder == self.highest_bidder
+        } else {
+            self.winning_indices().iter().any(|&i| self.bidders[i] == *bidder)
+        }
+    }
+
+    // the bid shared across the last winning place, if any, which only a random draw may order
+    pub fn boundary_tie(&self) -> Option<u64> {
+        let places = self.units as usize;
+        let mut sorted = self.bids.clone();
+        sorted.sort_unstable_by(|a, b| b.cmp(a));
+        if sorted.len() > places && sorted[places - 1] == sorted[places] {
+            Some(sorted[places - 1])
+        } else {
+            None
+        }
+    }
+
+    // settling a single-unit auction changes bids, so a tie can only be pending before that
+    pub fn tie_pending(&self) -> bool {
+        self.tie_break == TieBreak::Random
+            && !self.ties_drawn
+            && !self.item_withdrawn
+            && !self.winning_bid_withdrawn
+            && self.boundary_tie().is_some()
+    }
+}
+
+impl RevealedBid {
+    pub const LEN: usize = PUBLIC_KEY_LENGTH // bidder
+        + U64_LENGTH // bid
+        + U64_LENGTH // commit time
+        + U64_LENGTH // reveal time
+        + U64_LENGTH; // tie key
+}
+
+impl BundleItem {
+    pub const LEN: usize = PUBLIC_KEY_LENGTH // mint
+        + U64_LENGTH; // amount
+}
+
+impl CandleBid {
+    pub const LEN: usize = PUBLIC_KEY_LENGTH // bidder
+        + U64_LENGTH // bid
+        + U64_LENGTH; // time
 }
 
 impl SealedAuction {
     pub const LEN: usize = DISCRIMINATOR_LENGTH
         + BOOL_LENGTH // first price
+        + U8_LENGTH // commitment version
+        + ENUM_LENGTH // tie break
+        + U64_LENGTH // tie draw slot
+        + BOOL_LENGTH // ties drawn
         + PUBLIC_KEY_LENGTH // owner
         + PUBLIC_KEY_LENGTH // mint
         + U64_LENGTH // token amount
@@ -92,12 +723,357 @@ impl SealedAuction {
         + U64_LENGTH // end time
         + U64_LENGTH // reveal period
         + BOOL_LENGTH // cancelled
+        + ENUM_LENGTH // phase
         + STRING_LENGTH_PREFIX + MAX_TITLE_LENGTH // title
         + U64_LENGTH // bidder cap
         + PUBLIC_KEY_LENGTH // highest bidder
         + U64_LENGTH // highest bid
-        + U64_LENGTH // second highest bid
         + U64_LENGTH // bid floor
         + BOOL_LENGTH // winning bid withdrawn
+        + BOOL_LENGTH // item withdrawn
+        + BOOL_LENGTH // allow deposit decrease
+        + PUBLIC_KEY_LENGTH // auctioneer
+        + U64_LENGTH // penalty bps
+        + U64_LENGTH // penalty bond
+        + PUBLIC_KEY_LENGTH // penalty recipient
+        + U64_LENGTH // forfeited
+        + U8_LENGTH; // bump
+
+    // per bidder, each ciphertext has its own length prefix
+    pub fn ciphertext_space(auctioneer: &Pubkey) -> usize {
+        if *auctioneer == Pubkey::default() {
+            VECTOR_LENGTH_PREFIX
+        } else {
+            VECTOR_LENGTH_PREFIX + MAX_CIPHERTEXT_LENGTH
+        }
+    }
+
+    pub fn accepts_ciphertext(&self, ciphertext: &[u8]) -> bool {
+        if self.auctioneer == Pubkey::default() {
+            ciphertext.is_empty()
+        } else {
+            ciphertext.len() <= MAX_CIPHERTEXT_LENGTH
+        }
+    }
+
+    pub fn non_reveal_penalty(&self, fake_bid: u64) -> u64 {
+        if self.penalty_bond > 0 {
+            std::cmp::min(self.penalty_bond, fake_bid)
+        } else {
+            ((fake_bid as u128) * (self.penalty_bps as u128) / (BPS_DENOMINATOR as u128)) as u64
+        }
+    }
+
+    // equal bids are ranked by tie key and then stay in reveal order, returns the new entry's rank
+    pub fn record_reveal(&mut self, revealed_bid: RevealedBid) -> usize {
+        let rank = self.reveal_book.iter()
+            .take_while(|entry| {
+                entry.bid > revealed_bid.bid
+                    || (entry.bid == revealed_bid.bid && entry.tie_key <= revealed_bid.tie_key)
+            })
+            .count();
+        self.reveal_book.insert(rank, revealed_bid);
+
+        self.highest_bidder = self.reveal_book[0].bidder;
+        self.highest_bid = self.reveal_book[0].bid;
+        rank
+    }
+
+    // the winner's own bid in a first-price auction, otherwise the runner-up's
+    pub fn winning_payment(&self) -> u64 {
+        if self.first_price || self.reveal_book.len() < 2 {
+            self.highest_bid
+        } else {
+            self.reveal_book[1].bid
+        }
+    }
+
+    // entries at the top of the book sharing the highest bid
+    pub fn top_tie_len(&self) -> usize {
+        self.reveal_book.iter().take_while(|entry| entry.bid == self.highest_bid).count()
+    }
+
+    pub fn tie_pending(&self) -> bool {
+        self.tie_break == TieBreak::Random && !self.ties_drawn && self.top_tie_len() > 1
+    }
+
+    pub fn in_top_tie(&self, bidder: &Pubkey) -> bool {
+        self.reveal_book.iter().take(self.top_tie_len()).any(|entry| entry.bidder == *bidder)
+    }
+
+    pub fn accepts_deposit(&self, amount: u64) -> bool {
+        self.deposit_tiers.is_empty() || self.deposit_tiers.contains(&amount)
+    }
+
+    // a bid fits its tier when no lower tier would have covered it
+    pub fn fits_deposit_tier(&self, bid: u64, deposit: u64) -> bool {
+        self.deposit_tiers.iter().all(|&tier| tier >= deposit || tier < bid)
+    }
+
+    // the one place the phase moves, every instruction runs it first and again after changing
+    // cancelled, item_withdrawn or winning_bid_withdrawn
+    pub fn transition(&mut self, cur_time: u64) -> Phase {
+        self.phase = if self.cancelled {
+            Phase::Cancelled
+        } else if self.item_withdrawn && (self.winning_bid_withdrawn || self.highest_bid == 0) {
+            Phase::Settled
+        } else if cur_time <= self.start_time {
+            Phase::Scheduled
+        } else if cur_time < self.end_time {
+            Phase::Bidding
+        } else if cur_time < self.reveal_period {
+            Phase::Revealing
+        } else {
+            Phase::Settling
+        };
+
+        self.phase
+    }
+
+    pub fn require_phase(&mut self, cur_time: u64, allowed: &[Phase]) -> ProgramResult {
+        let phase = self.transition(cur_time);
+        if allowed.contains(&phase) {
+            return Ok(());
+        }
+
+        let err = match phase {
+            Phase::Scheduled => AuctionError::PhaseScheduled,
+            Phase::Bidding => AuctionError::PhaseBidding,
+            Phase::Revealing => AuctionError::PhaseRevealing,
+            Phase::Settling => AuctionError::PhaseSettling,
+            Phase::Settled => AuctionError::PhaseSettled,
+            Phase::Cancelled => AuctionError::AuctionCancelled,
+        };
+        Err(err.into())
+    }
+}
+
+impl DutchAuction {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH // owner
+        + PUBLIC_KEY_LENGTH // mint
+        + U64_LENGTH // token amount
+        + U64_LENGTH // start time
+        + U64_LENGTH // end time
+        + BOOL_LENGTH // cancelled
+        + STRING_LENGTH_PREFIX + MAX_TITLE_LENGTH // title
+        + U64_LENGTH // start price
+        + U64_LENGTH // bid floor
+        + ENUM_LENGTH // decay curve
+        + U64_LENGTH // half life
+        + PUBLIC_KEY_LENGTH // buyer
+        + U64_LENGTH // sale price
+        + BOOL_LENGTH // sold
+        + U8_LENGTH; // bump
+}
+
+impl MultiUnitSealedAuction {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH
+        + ENUM_LENGTH // clearing rule
+        + PUBLIC_KEY_LENGTH // owner
+        + PUBLIC_KEY_LENGTH // mint
+        + U64_LENGTH // token amount
+        + U64_LENGTH // units
+        + U64_LENGTH // start time
+        + U64_LENGTH // end time
+        + U64_LENGTH // reveal period
+        + BOOL_LENGTH // cancelled
+        + STRING_LENGTH_PREFIX + MAX_TITLE_LENGTH // title
+        + U64_LENGTH // bidder cap
+        + U64_LENGTH // bid floor
+        + BOOL_LENGTH // settled
+        + U64_LENGTH // clearing price
+        + U64_LENGTH // units sold
+        + BOOL_LENGTH // proceeds withdrawn
+        + BOOL_LENGTH // item reclaimed
+        + U8_LENGTH; // bump
+}
+
+impl ReverseAuction {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH // owner
+        + PUBLIC_KEY_LENGTH // mint
+        + U64_LENGTH // token amount
+        + U64_LENGTH // budget
+        + U64_LENGTH // start time
+        + U64_LENGTH // end time
+        + BOOL_LENGTH // cancelled
+        + STRING_LENGTH_PREFIX + MAX_TITLE_LENGTH // title
+        + U64_LENGTH // supplier cap
+        + PUBLIC_KEY_LENGTH // lowest supplier
+        + U64_LENGTH // lowest offer
+        + U64_LENGTH // min offer decrement
+        + BOOL_LENGTH // item withdrawn
+        + BOOL_LENGTH // payment withdrawn
+        + BOOL_LENGTH // budget reclaimed
+        + U8_LENGTH; // bump
+}
+
+impl ClockAuction {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH // owner
+        + PUBLIC_KEY_LENGTH // mint
+        + U64_LENGTH // token amount
+        + U64_LENGTH // start time
+        + BOOL_LENGTH // cancelled
+        + STRING_LENGTH_PREFIX + MAX_TITLE_LENGTH // title
+        + U64_LENGTH // bid floor
+        + U64_LENGTH // price increment
+        + U64_LENGTH // step length
+        + U64_LENGTH // bidder cap
+        + BOOL_LENGTH // settled
+        + PUBLIC_KEY_LENGTH // winner
+        + U64_LENGTH // clearing price
+        + BOOL_LENGTH // winning bid withdrawn
+        + BOOL_LENGTH // item withdrawn
+        + U8_LENGTH; // bump
+
+    pub fn clock_price(&self, cur_time: u64) -> u64 {
+        if cur_time <= self.start_time {
+            self.bid_floor
+        } else {
+            self.bid_floor + self.price_increment * ((cur_time - self.start_time) / self.step_length)
+        }
+    }
+
+    // highest clock price the deposit covers
+    pub fn affordable_price(&self, deposit: u64) -> u64 {
+        self.bid_floor + self.price_increment * ((deposit - self.bid_floor) / self.price_increment)
+    }
+
+    // bidders whose deposit no longer covers the clock are out at the last price they could afford
+    pub fn exit_price(&self, index: usize, cur_time: u64) -> Option<u64> {
+        if self.exit_prices[index] > 0 {
+            Some(self.exit_prices[index])
+        } else if self.deposits[index] < self.clock_price(cur_time) {
+            Some(self.affordable_price(self.deposits[index]))
+        } else {
+            None
+        }
+    }
+
+    pub fn active_bidders(&self, cur_time: u64) -> usize {
+        (0..self.bidders.len())
+            .filter(|&i| self.exit_price(i, cur_time).is_none())
+            .count()
+    }
+}
+
+impl Raffle {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH // owner
+        + PUBLIC_KEY_LENGTH // mint
+        + U64_LENGTH // token amount
+        + U64_LENGTH // start time
+        + U64_LENGTH // end time
+        + BOOL_LENGTH // cancelled
+        + STRING_LENGTH_PREFIX + MAX_TITLE_LENGTH // title
+        + U64_LENGTH // ticket price
+        + U64_LENGTH // ticket cap
+        + U64_LENGTH // min tickets
+        + U64_LENGTH // tickets sold
+        + U64_LENGTH // buyer cap
+        + U64_LENGTH // draw slot
+        + BOOL_LENGTH // drawn
+        + PUBLIC_KEY_LENGTH // winner
+        + BOOL_LENGTH // proceeds withdrawn
+        + BOOL_LENGTH // item withdrawn
+        + U8_LENGTH; // bump
+
+    // cancelled or closed without selling the minimum, so everything goes back
+    pub fn refunding(&self, cur_time: u64) -> bool {
+        self.cancelled || (cur_time > self.end_time && self.tickets_sold < self.min_tickets)
+    }
+}
+
+impl PackageSealedAuction {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH // owner
+        + U64_LENGTH // start time
+        + U64_LENGTH // end time
+        + U64_LENGTH // reveal period
+        + BOOL_LENGTH // cancelled
+        + STRING_LENGTH_PREFIX + MAX_TITLE_LENGTH // title
+        + U64_LENGTH // bidder cap
+        + U64_LENGTH // bid floor
+        + BOOL_LENGTH // settled
+        + U64_LENGTH // revenue
+        + BOOL_LENGTH // proceeds withdrawn
+        + BOOL_LENGTH // item reclaimed
+        + U8_LENGTH; // bump
+
+    // lots that make up a package, in lot order
+    pub fn package_lots(&self, package: u8) -> Vec<BundleItem> {
+        self.lots.iter().enumerate()
+            .filter(|&(i, _)| package & (1 << i) != 0)
+            .map(|(_, lot)| *lot)
+            .collect()
+    }
+}
+
+impl CatalogBid {
+    pub const LEN: usize = U32_LENGTH // bidder index
+        + U64_LENGTH; // bid
+}
+
+impl CatalogLot {
+    pub const LEN: usize = PUBLIC_KEY_LENGTH // mint
+        + U64_LENGTH // amount
+        + PUBLIC_KEY_LENGTH // highest bidder
+        + U64_LENGTH // highest bid
+        + BOOL_LENGTH // item withdrawn
+        + BOOL_LENGTH; // winning bid withdrawn
+
+    pub fn bid_position(&self, bidder_index: usize) -> Option<usize> {
+        self.bids.iter().position(|entry| entry.bidder_index as usize == bidder_index)
+    }
+}
+
+impl CatalogAuction {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH // owner
+        + U64_LENGTH // start time
+        + U64_LENGTH // end time
+        + BOOL_LENGTH // cancelled
+        + STRING_LENGTH_PREFIX + MAX_TITLE_LENGTH // title
+        + U64_LENGTH // lot cap
+        + U64_LENGTH // bidder cap
+        + U64_LENGTH // lot bidder cap
+        + U64_LENGTH // bid floor
+        + U64_LENGTH // min bid increment
+        + U8_LENGTH; // bump
+
+    // full size with every lot, bidder, and per-lot bid slot allocated
+    pub fn space(lot_cap: u64, bidder_cap: u64, lot_bidder_cap: u64) -> usize {
+        let lot_space = CatalogLot::LEN + VECTOR_LENGTH_PREFIX + (lot_bidder_cap as usize)*CatalogBid::LEN;
+        CatalogAuction::LEN
+            + VECTOR_LENGTH_PREFIX + (lot_cap as usize)*lot_space
+            + VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*PUBLIC_KEY_LENGTH
+    }
+}
+
+impl SeriesAuction {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH // owner
+        + PUBLIC_KEY_LENGTH // mint
+        + U64_LENGTH // token amount
+        + STRING_LENGTH_PREFIX + MAX_TITLE_LENGTH // title
+        + PUBLIC_KEY_LENGTH // config
+        + U64_LENGTH // round
+        + U64_LENGTH // round start
+        + U64_LENGTH // round end
+        + BOOL_LENGTH // round settled
+        + BOOL_LENGTH // stopped
+        + PUBLIC_KEY_LENGTH // highest bidder
+        + U64_LENGTH // highest bid
+        + U8_LENGTH; // bump
+}
+
+impl SeriesConfig {
+    pub const LEN: usize = DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH // auction
+        + U64_LENGTH // round duration
+        + U64_LENGTH // bid floor
+        + U64_LENGTH // min bid increment
         + U8_LENGTH; // bump
 }
\ No newline at end of file
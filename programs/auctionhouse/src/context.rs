@@ -13,7 +13,8 @@ use crate::utils::*;
     start_time: u64,
     end_time: u64,
     bidder_cap: u64,
-    token_amount: u64
+    token_amount: u64,
+    options: OpenAuctionOptions
 )]
 pub struct CreateOpenAuction<'info> {
     #[account(init,
@@ -22,7 +23,11 @@ pub struct CreateOpenAuction<'info> {
         payer = owner,
         space = OpenAuction::LEN +
         VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*PUBLIC_KEY_LENGTH +
-        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH)]
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH +
+        VECTOR_LENGTH_PREFIX + options.candle.map_or(0, |candle| candle.log_cap as usize)*CandleBid::LEN +
+        VECTOR_LENGTH_PREFIX + options.bundle_amounts.len()*BundleItem::LEN +
+        VECTOR_LENGTH_PREFIX + (options.units as usize)*PUBLIC_KEY_LENGTH)]
     pub auction: Account<'info, OpenAuction>,
     #[account(mut)]
     pub auction_ata: AccountInfo<'info>,
@@ -60,6 +65,27 @@ pub struct MakeOpenBid<'info> {
     pub system_program: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct BuyNowOpen<'info> {
+    #[account(mut, has_one = mint)]
+    pub auction: Account<'info, OpenAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(mut)]
+    pub buyer_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
 #[derive(Accounts)]
 pub struct ReclaimOpenBid<'info> {
     #[account(mut)]
@@ -70,6 +96,47 @@ pub struct ReclaimOpenBid<'info> {
     pub system_program: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct CommitCandleDrawOpen<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, OpenAuction>,
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveCandleOpen<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, OpenAuction>,
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitTieBreakDrawOpen<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, OpenAuction>,
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DrawTieBreakOpen<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, OpenAuction>,
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealReserveOpen<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, OpenAuction>,
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct WithdrawItemOpen<'info> {
     #[account(mut, has_one = highest_bidder, has_one = mint)]
@@ -91,6 +158,27 @@ pub struct WithdrawItemOpen<'info> {
     pub rent_sysvar: AccountInfo<'info>
 }
 
+#[derive(Accounts)]
+pub struct WithdrawUnitOpen<'info> {
+    #[account(mut, has_one = mint)]
+    pub auction: Account<'info, OpenAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(mut)]
+    pub bidder_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
 #[derive(Accounts)]
 pub struct WithdrawWinningBidOpen<'info> {
     #[account(mut, has_one = owner)]
@@ -132,7 +220,8 @@ pub struct ReclaimItemOpen<'info> {
     end_time: u64,
     reveal_period: u64,
     bidder_cap: u64,
-    token_amount: u64
+    token_amount: u64,
+    options: SealedAuctionOptions
 )]
 pub struct CreateSealedAuction<'info> {
     #[account(init,
@@ -142,7 +231,13 @@ pub struct CreateSealedAuction<'info> {
         space = SealedAuction::LEN +
         VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*PUBLIC_KEY_LENGTH +
         VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U8_LENGTH*32 +
-        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH)]
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*BOOL_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*RevealedBid::LEN +
+        VECTOR_LENGTH_PREFIX + options.bundle_amounts.len()*BundleItem::LEN +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*SealedAuction::ciphertext_space(&options.auctioneer.unwrap_or_default()) +
+        VECTOR_LENGTH_PREFIX + options.deposits.as_ref().map_or(0, |deposits| deposits.tiers.len())*U64_LENGTH)]
     pub auction: Account<'info, SealedAuction>,
     #[account(mut)]
     pub auction_ata: AccountInfo<'info>,
@@ -180,6 +275,16 @@ pub struct MakeSealedBid<'info> {
     pub system_program: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct UpdateSealedBid<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, SealedAuction>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct ReclaimSealedBid<'info> {
     #[account(mut)]
@@ -190,6 +295,43 @@ pub struct ReclaimSealedBid<'info> {
     pub system_program: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct WithdrawForfeitsSealed<'info> {
+    #[account(mut, has_one = penalty_recipient)]
+    pub auction: Account<'info, SealedAuction>,
+    #[account(mut)]
+    pub penalty_recipient: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ForfeitUnrevealedSealed<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, SealedAuction>,
+    // checked against the auction's bidders, refunds go here
+    #[account(mut)]
+    pub bidder: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitTieBreakDrawSealed<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, SealedAuction>,
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DrawTieBreakSealed<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, SealedAuction>,
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct RevealSealedBid<'info> {
     #[account(mut)]
@@ -200,6 +342,31 @@ pub struct RevealSealedBid<'info> {
     pub system_program: AccountInfo<'info>,
 }
 
+#[derive(Accounts)]
+pub struct RevealSealedBidFor<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, SealedAuction>,
+    // receives the refund if the revealed bid loses
+    #[account(mut)]
+    pub bidder: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealSealedBidSigned<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, SealedAuction>,
+    // signs off-chain, the ed25519 instruction before this one carries their signature
+    #[account(mut)]
+    pub bidder: AccountInfo<'info>,
+    pub relayer: Signer<'info>,
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
 #[derive(Accounts)]
 pub struct WithdrawItemSealed<'info> {
     #[account(mut, has_one = highest_bidder, has_one = mint)]
@@ -250,4 +417,960 @@ pub struct ReclaimItemSealed<'info> {
     pub system_program: AccountInfo<'info>,
     #[account(address = sysvar::rent::ID)]
     pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    bump: u8,
+    title: String,
+    start_price: u64,
+    floor: u64,
+    decay_curve: DecayCurve,
+    half_life: u64,
+    start_time: u64,
+    end_time: u64,
+    token_amount: u64
+)]
+pub struct CreateDutchAuction<'info> {
+    #[account(init,
+        seeds=[b"dutch auction", owner.to_account_info().key.as_ref(), name_seed(&title)],
+        bump = bump,
+        payer = owner,
+        space = DutchAuction::LEN)]
+    pub auction: Account<'info, DutchAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct CancelDutchAuction<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, DutchAuction>,
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BuyDutch<'info> {
+    #[account(mut, has_one = owner, has_one = mint)]
+    pub auction: Account<'info, DutchAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(mut)]
+    pub buyer_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct ReclaimItemDutch<'info> {
+    #[account(mut, has_one = owner, has_one = mint)]
+    pub auction: Account<'info, DutchAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    bump: u8,
+    title: String,
+    floor: u64,
+    clearing_rule: ClearingRule,
+    start_time: u64,
+    end_time: u64,
+    reveal_period: u64,
+    bidder_cap: u64,
+    token_amount: u64,
+    units: u64
+)]
+pub struct CreateMultiUnitSealedAuction<'info> {
+    #[account(init,
+        seeds=[b"multi unit sealed auction", owner.to_account_info().key.as_ref(), name_seed(&title)],
+        bump = bump,
+        payer = owner,
+        space = MultiUnitSealedAuction::LEN +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*PUBLIC_KEY_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U8_LENGTH*32 +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH)]
+    pub auction: Account<'info, MultiUnitSealedAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct CancelMultiUnitSealedAuction<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, MultiUnitSealedAuction>,
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MakeMultiUnitSealedBid<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, MultiUnitSealedAuction>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimMultiUnitSealedBid<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, MultiUnitSealedAuction>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealMultiUnitSealedBid<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, MultiUnitSealedAuction>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleMultiUnitSealedAuction<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, MultiUnitSealedAuction>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawMultiUnitSealed<'info> {
+    #[account(mut, has_one = mint)]
+    pub auction: Account<'info, MultiUnitSealedAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(mut)]
+    pub bidder_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProceedsMultiUnitSealed<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, MultiUnitSealedAuction>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimItemMultiUnitSealed<'info> {
+    #[account(mut, has_one = owner, has_one = mint)]
+    pub auction: Account<'info, MultiUnitSealedAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    bump: u8,
+    title: String,
+    budget: u64,
+    decrement: u64,
+    start_time: u64,
+    end_time: u64,
+    supplier_cap: u64,
+    token_amount: u64
+)]
+pub struct CreateReverseAuction<'info> {
+    #[account(init,
+        seeds=[b"reverse auction", owner.to_account_info().key.as_ref(), name_seed(&title)],
+        bump = bump,
+        payer = owner,
+        space = ReverseAuction::LEN +
+        VECTOR_LENGTH_PREFIX + (supplier_cap as usize)*PUBLIC_KEY_LENGTH +
+        VECTOR_LENGTH_PREFIX + (supplier_cap as usize)*U64_LENGTH)]
+    pub auction: Account<'info, ReverseAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct CancelReverseAuction<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, ReverseAuction>,
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MakeReverseOffer<'info> {
+    #[account(mut, has_one = mint)]
+    pub auction: Account<'info, ReverseAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub supplier: Signer<'info>,
+    #[account(mut)]
+    pub supplier_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimReverseOffer<'info> {
+    #[account(mut, has_one = mint)]
+    pub auction: Account<'info, ReverseAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub supplier: Signer<'info>,
+    #[account(mut)]
+    pub supplier_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct WithdrawItemReverse<'info> {
+    #[account(mut, has_one = owner, has_one = mint)]
+    pub auction: Account<'info, ReverseAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPaymentReverse<'info> {
+    #[account(mut, has_one = lowest_supplier)]
+    pub auction: Account<'info, ReverseAuction>,
+    #[account(mut)]
+    pub lowest_supplier: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimBudgetReverse<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, ReverseAuction>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(
+    bump: u8,
+    title: String,
+    floor: u64,
+    increment: u64,
+    step_length: u64,
+    start_time: u64,
+    bidder_cap: u64,
+    token_amount: u64
+)]
+pub struct CreateClockAuction<'info> {
+    #[account(init,
+        seeds=[b"clock auction", owner.to_account_info().key.as_ref(), name_seed(&title)],
+        bump = bump,
+        payer = owner,
+        space = ClockAuction::LEN +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*PUBLIC_KEY_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH)]
+    pub auction: Account<'info, ClockAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct CancelClockAuction<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, ClockAuction>,
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterClockBidder<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, ClockAuction>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TopUpClockDeposit<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, ClockAuction>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DropOutClock<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, ClockAuction>,
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleClockAuction<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, ClockAuction>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimClockDeposit<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, ClockAuction>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawItemClock<'info> {
+    #[account(mut, has_one = winner, has_one = mint)]
+    pub auction: Account<'info, ClockAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub winner: Signer<'info>,
+    #[account(mut)]
+    pub winner_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct WithdrawWinningBidClock<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, ClockAuction>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimItemClock<'info> {
+    #[account(mut, has_one = owner, has_one = mint)]
+    pub auction: Account<'info, ClockAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    bump: u8,
+    title: String,
+    ticket_price: u64,
+    ticket_cap: u64,
+    min_tickets: u64,
+    start_time: u64,
+    end_time: u64,
+    buyer_cap: u64,
+    token_amount: u64
+)]
+pub struct CreateRaffle<'info> {
+    #[account(init,
+        seeds=[b"raffle", owner.to_account_info().key.as_ref(), name_seed(&title)],
+        bump = bump,
+        payer = owner,
+        space = Raffle::LEN +
+        VECTOR_LENGTH_PREFIX + (buyer_cap as usize)*PUBLIC_KEY_LENGTH +
+        VECTOR_LENGTH_PREFIX + (buyer_cap as usize)*U64_LENGTH)]
+    pub auction: Account<'info, Raffle>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct CancelRaffle<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, Raffle>,
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BuyRaffleTickets<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, Raffle>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitRaffleDraw<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, Raffle>,
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct DrawRaffle<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, Raffle>,
+    #[account(address = sysvar::slot_hashes::ID)]
+    pub slot_hashes: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimRaffleTickets<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, Raffle>,
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawItemRaffle<'info> {
+    #[account(mut, has_one = winner, has_one = mint)]
+    pub auction: Account<'info, Raffle>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub winner: Signer<'info>,
+    #[account(mut)]
+    pub winner_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProceedsRaffle<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, Raffle>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimItemRaffle<'info> {
+    #[account(mut, has_one = owner, has_one = mint)]
+    pub auction: Account<'info, Raffle>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    bump: u8,
+    title: String,
+    floor: u64,
+    start_time: u64,
+    end_time: u64,
+    reveal_period: u64,
+    bidder_cap: u64,
+    lot_amounts: Vec<u64>
+)]
+pub struct CreatePackageSealedAuction<'info> {
+    #[account(init,
+        seeds=[b"package sealed auction", owner.to_account_info().key.as_ref(), name_seed(&title)],
+        bump = bump,
+        payer = owner,
+        space = PackageSealedAuction::LEN +
+        VECTOR_LENGTH_PREFIX + lot_amounts.len()*BundleItem::LEN +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*PUBLIC_KEY_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U8_LENGTH*32 +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U8_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*U64_LENGTH +
+        VECTOR_LENGTH_PREFIX + (bidder_cap as usize)*BOOL_LENGTH)]
+    pub auction: Account<'info, PackageSealedAuction>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct CancelPackageSealedAuction<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, PackageSealedAuction>,
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MakePackageSealedBid<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, PackageSealedAuction>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimPackageSealedBid<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, PackageSealedAuction>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RevealPackageSealedBid<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, PackageSealedAuction>,
+    pub bidder: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettlePackageSealedAuction<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, PackageSealedAuction>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPackageSealed<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, PackageSealedAuction>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProceedsPackageSealed<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, PackageSealedAuction>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimItemPackageSealed<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, PackageSealedAuction>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    bump: u8,
+    title: String,
+    floor: u64,
+    increment: u64,
+    start_time: u64,
+    end_time: u64,
+    bidder_cap: u64,
+    lot_cap: u64,
+    lot_bidder_cap: u64
+)]
+pub struct CreateCatalogAuction<'info> {
+    // clamped so init succeeds and create_catalog_auction can fail with CatalogTooLarge instead
+    #[account(init,
+        seeds=[b"catalog auction", owner.to_account_info().key.as_ref(), name_seed(&title)],
+        bump = bump,
+        payer = owner,
+        space = CatalogAuction::space(lot_cap, bidder_cap, lot_bidder_cap).min(MAX_INIT_ACCOUNT_SIZE))]
+    pub auction: Account<'info, CatalogAuction>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct AddCatalogLot<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, CatalogAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct CancelCatalogAuction<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, CatalogAuction>,
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MakeCatalogBid<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, CatalogAuction>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimCatalogBid<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, CatalogAuction>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawItemCatalog<'info> {
+    #[account(mut)]
+    pub auction: Account<'info, CatalogAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub highest_bidder: Signer<'info>,
+    #[account(mut)]
+    pub highest_bidder_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+pub struct WithdrawWinningBidCatalog<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, CatalogAuction>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ReclaimItemCatalog<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, CatalogAuction>,
+    #[account(mut)]
+    pub auction_ata: AccountInfo<'info>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(mut)]
+    pub owner_ata: AccountInfo<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
+}
+
+#[derive(Accounts)]
+#[instruction(
+    bump: u8,
+    config_bump: u8,
+    title: String,
+    floor: u64,
+    increment: u64,
+    round_duration: u64,
+    start_time: u64,
+    token_amount: u64
+)]
+pub struct CreateSeriesAuction<'info> {
+    #[account(init,
+        seeds=[b"series auction", owner.to_account_info().key.as_ref(), name_seed(&title)],
+        bump = bump,
+        payer = owner,
+        space = SeriesAuction::LEN)]
+    pub auction: Account<'info, SeriesAuction>,
+    #[account(init,
+        seeds=[b"series config", auction.to_account_info().key.as_ref()],
+        bump = config_bump,
+        payer = owner,
+        space = SeriesConfig::LEN)]
+    pub config: Account<'info, SeriesConfig>,
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub mint: Account<'info, Mint>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct StopSeriesAuction<'info> {
+    #[account(mut, has_one = owner)]
+    pub auction: Account<'info, SeriesAuction>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MakeSeriesBid<'info> {
+    #[account(mut, has_one = config)]
+    pub auction: Account<'info, SeriesAuction>,
+    pub config: Account<'info, SeriesConfig>,
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    // refunded in the same instruction, ignored when there's no bid yet
+    #[account(mut)]
+    pub previous_bidder: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SettleSeriesRound<'info> {
+    #[account(mut, has_one = owner, has_one = mint, has_one = config)]
+    pub auction: Account<'info, SeriesAuction>,
+    pub config: Account<'info, SeriesConfig>,
+    #[account(mut)]
+    pub owner: AccountInfo<'info>,
+    #[account(mut)]
+    pub mint: Account<'info, Mint>,
+    // ignored when the round got no bids
+    #[account(mut)]
+    pub highest_bidder: AccountInfo<'info>,
+    #[account(mut)]
+    pub highest_bidder_ata: AccountInfo<'info>,
+    // anyone can settle and pays for the winner's ata if it doesn't exist
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: AccountInfo<'info>,
+    #[account(address = spl_associated_token_account::ID)]
+    pub ata_program: AccountInfo<'info>,
+    #[account(address = system_program::ID)]
+    pub system_program: AccountInfo<'info>,
+    #[account(address = sysvar::rent::ID)]
+    pub rent_sysvar: AccountInfo<'info>
 }
\ No newline at end of file
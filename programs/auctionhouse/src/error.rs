@@ -56,4 +56,198 @@ pub enum AuctionError {
     CannotCancelAfterClose,
     #[msg("Sealed bid cannot be higher than escrowed SOL.")]
     InsufficientSol,
+    #[msg("Start price must be greater than the floor.")]
+    InvalidStartPrice,
+    #[msg("Exponential decay requires a half-life greater than 0.")]
+    InvalidHalfLife,
+    #[msg("Item has already been sold.")]
+    ItemAlreadySold,
+    #[msg("Extension length must be greater than 0 when an extension window is set.")]
+    InvalidExtension,
+    #[msg("Buy-now price must be greater than the floor.")]
+    InvalidBuyNowPrice,
+    #[msg("Buy-now cutoff must be between 1 and 10000 basis points.")]
+    InvalidBuyNowCutoff,
+    #[msg("Buy-now is disabled or bidding has passed the buy-now cutoff.")]
+    BuyNowUnavailable,
+    #[msg("Item has already been withdrawn.")]
+    ItemAlreadyWithdrawn,
+    #[msg("Reserve reveal window must be greater than 0 when a hidden reserve is set.")]
+    InvalidReserveRevealWindow,
+    #[msg("Auction does not have a hidden reserve.")]
+    NoReserve,
+    #[msg("Reserve has already been revealed.")]
+    ReserveAlreadyRevealed,
+    #[msg("Reserve reveal window has elapsed.")]
+    ReserveRevealWindowOver,
+    #[msg("Keccak256 of provided reserve and nonce does not match the reserve hash.")]
+    ReserveHashMismatch,
+    #[msg("Seller has not yet revealed the reserve.")]
+    ReservePending,
+    #[msg("Highest bid did not meet the reserve.")]
+    ReserveNotMet,
+    #[msg("Units must be greater than 0 and evenly divide the SPL token amount.")]
+    InvalidUnits,
+    #[msg("Revealed quantity must be between 1 and the number of units for sale.")]
+    InvalidBidUnits,
+    #[msg("Auction has already been settled.")]
+    AlreadySettled,
+    #[msg("Auction has not been settled.")]
+    NotSettled,
+    #[msg("Nothing left to withdraw for this key.")]
+    NothingToWithdraw,
+    #[msg("Item has already been reclaimed.")]
+    ItemAlreadyReclaimed,
+    #[msg("Candle window must be shorter than the remaining auction and needs a non-zero log cap.")]
+    InvalidCandleWindow,
+    #[msg("Candle auctions cannot use soft close extensions or buy-now.")]
+    CandleIncompatible,
+    #[msg("Candle close has not been drawn yet.")]
+    CandleNotResolved,
+    #[msg("Candle close has already been drawn.")]
+    CandleAlreadyResolved,
+    #[msg("Auction is not a candle auction.")]
+    NotCandleAuction,
+    #[msg("Bids logged during the candle window cannot be withdrawn until the close is drawn.")]
+    CandleBidLocked,
+    #[msg("Budget must be at least 1 lamport.")]
+    InvalidBudget,
+    #[msg("Minimum offer decrement must be greater than 0.")]
+    InvalidDecrement,
+    #[msg("Offer must be greater than 0 and within the budget.")]
+    OfferOverBudget,
+    #[msg("Must offer at least min_offer_decrement lower than lowest_offer.")]
+    InsufficientOffer,
+    #[msg("No winning offer.")]
+    NoWinningOffer,
+    #[msg("Winning offer payment has already been withdrawn.")]
+    AlreadyWithdrewPayment,
+    #[msg("Budget has already been reclaimed.")]
+    BudgetAlreadyReclaimed,
+    #[msg("Clock step length must be greater than 0.")]
+    InvalidStepLength,
+    #[msg("Clock has already started.")]
+    ClockStarted,
+    #[msg("Clock has not started yet.")]
+    ClockNotStarted,
+    #[msg("Key is already registered for this auction.")]
+    AlreadyRegistered,
+    #[msg("Bidder has already dropped out.")]
+    BidderDroppedOut,
+    #[msg("Only one bidder remains and the auction must be settled.")]
+    LastBidderRemaining,
+    #[msg("More than one bidder is still in the auction.")]
+    ClockStillRunning,
+    #[msg("Ticket price must be at least 1 lamport.")]
+    InvalidTicketPrice,
+    #[msg("Minimum ticket count must be between 1 and the ticket cap.")]
+    InvalidMinTickets,
+    #[msg("Must buy at least one ticket.")]
+    InvalidTicketCount,
+    #[msg("Not enough tickets left.")]
+    TicketCapReached,
+    #[msg("Raffle did not sell the minimum number of tickets.")]
+    MinTicketsNotReached,
+    #[msg("Raffle has already been drawn.")]
+    AlreadyDrawn,
+    #[msg("Raffle has not been drawn.")]
+    NotDrawn,
+    #[msg("Draw slot has not been committed.")]
+    DrawNotCommitted,
+    #[msg("Draw slot is still pending or its hash has not aged out yet.")]
+    DrawSlotPending,
+    #[msg("Draw slot hash is no longer available, commit a new draw slot.")]
+    DrawSlotExpired,
+    #[msg("Raffle did not close in a refund.")]
+    NotRefunding,
+    #[msg("Remaining accounts must be a mint and two token accounts for each bundle item.")]
+    InvalidBundleAccounts,
+    #[msg("Each mint can only appear once in a lot.")]
+    DuplicateBundleMint,
+    #[msg("Package auctions need between 1 and 8 lots.")]
+    InvalidLots,
+    #[msg("Package auctions allow at most 16 bidders.")]
+    InvalidPackageBidderCap,
+    #[msg("Package must be a non-empty set of the auction's lots.")]
+    InvalidPackage,
+    #[msg("Lot cap must be greater than 0.")]
+    InvalidLotCap,
+    #[msg("Maximum number of lots has been reached.")]
+    LotCapReached,
+    #[msg("Lots can only be added before the first bid.")]
+    LotsLocked,
+    #[msg("No lot with this index, mint, and token account.")]
+    InvalidLot,
+    #[msg("Round duration must be greater than 0.")]
+    InvalidRoundDuration,
+    #[msg("Mint authority must be the series auction PDA.")]
+    InvalidMintAuthority,
+    #[msg("Series has been stopped.")]
+    SeriesStopped,
+    #[msg("Account does not match the round's highest bidder.")]
+    HighestBidderMismatch,
+    #[msg("Multi-unit open auctions can't use proxy bidding, buy-now, a hidden reserve, a candle close, or a bundle.")]
+    MultiUnitIncompatible,
+    #[msg("Bidder is not in the winning set.")]
+    NotWinningBidder,
+    #[msg("Use withdraw_item_open for single-unit auctions and withdraw_unit_open for multi-unit ones.")]
+    WrongUnitWithdraw,
+    #[msg("VCG auctions allow at most 16 bidders.")]
+    InvalidVcgBidderCap,
+    #[msg("Unknown bid commitment version.")]
+    InvalidCommitmentVersion,
+    #[msg("Bid must be revealed with the auction's commitment version.")]
+    WrongCommitmentVersion,
+    #[msg("Penalty must be either basis points up to 10000 or a fixed bond, not both.")]
+    InvalidPenalty,
+    #[msg("Deposit must cover the non-reveal bond.")]
+    UnderPenaltyBond,
+    #[msg("Bid must be revealed before it can be reclaimed during the reveal period.")]
+    RevealPending,
+    #[msg("Seller does not allow lowering a sealed bid deposit.")]
+    DepositDecreaseDisallowed,
+    #[msg("Auctioneer reveals require commitment version 2.")]
+    AuctioneerRequiresV2,
+    #[msg("Ciphertext must be at most 128 bytes and only sent to auctions with an auctioneer.")]
+    InvalidCiphertext,
+    #[msg("Auction has no auctioneer key.")]
+    NoAuctioneer,
+    #[msg("Not allowed before the auction starts.")]
+    PhaseScheduled,
+    #[msg("Not allowed while bidding is open.")]
+    PhaseBidding,
+    #[msg("Not allowed during the reveal period.")]
+    PhaseRevealing,
+    #[msg("Not allowed once the reveal period is over.")]
+    PhaseSettling,
+    #[msg("Auction is already settled.")]
+    PhaseSettled,
+    #[msg("Deposit tiers must be ascending, above the bid floor and the penalty bond, and at most 8.")]
+    InvalidDepositTiers,
+    #[msg("Deposit must be one of the auction's deposit tiers.")]
+    NotADepositTier,
+    #[msg("Bid must be above the next lower deposit tier.")]
+    BidOutsideDepositTier,
+    #[msg("Reveal must directly follow an ed25519 instruction with the bidder's signature over the reveal.")]
+    InvalidRevealSignature,
+    #[msg("Bid has already been revealed.")]
+    BidAlreadyRevealed,
+    #[msg("Token account is not the auction's associated token account.")]
+    InvalidAuctionAta,
+    #[msg("Catalog auction would be larger than 10 KiB, lower the lot cap or bidder caps.")]
+    CatalogTooLarge,
+    #[msg("Maximum number of bidders on this lot has been reached.")]
+    LotBidderCapReached,
+    #[msg("A non-reveal penalty needs a penalty recipient.")]
+    MissingPenaltyRecipient,
+    #[msg("Only bids that were never revealed can be forfeited.")]
+    BidNotForfeitable,
+    #[msg("Equal bids are waiting on the random tie-break draw.")]
+    TieNotDrawn,
+    #[msg("No equal bids are waiting on a random tie-break draw.")]
+    NoTieToDraw,
+    #[msg("A candle close can't use the random tie-break.")]
+    RandomTieBreakWithCandle,
+    #[msg("Token account is not the winner's associated token account.")]
+    InvalidWinnerAta,
 }
\ No newline at end of file
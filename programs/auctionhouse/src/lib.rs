@@ -3,6 +3,8 @@ pub mod context;
 pub mod error;
 pub mod utils;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
 use account::*;
 use context::*;
 use error::*;
@@ -13,8 +15,8 @@ declare_id!("6tEWNsQDT8KZ2EDZRBa4CHRTxPESk6tvSJEwiddwSxkh");
 #[program]
 pub mod auctionhouse {
     use super::*;
-    pub fn create_open_auction(
-        ctx: Context<CreateOpenAuction>,
+    pub fn create_open_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateOpenAuction<'info>>,
         bump: u8,
         title: String,
         floor: u64,
@@ -23,6 +25,7 @@ pub mod auctionhouse {
         end_time: u64,
         bidder_cap: u64,
         token_amount: u64,
+        options: OpenAuctionOptions,
     ) -> ProgramResult {
         let auction: &mut Account<OpenAuction> = &mut ctx.accounts.auction;
         let auction_ata = &ctx.accounts.auction_ata;
@@ -37,6 +40,15 @@ pub mod auctionhouse {
         let clock: Clock = Clock::get().unwrap();
         let cur_time: u64 = clock.unix_timestamp as u64;
 
+        let OpenAuctionOptions {
+            soft_close, buy_now, reserve, proxy_bidding, candle, bundle_amounts, units, tie_break
+        } = options;
+        // a config left as None is off, the same as an all-zero one
+        let soft_close = soft_close.unwrap_or_default();
+        let buy_now = buy_now.unwrap_or_default();
+        let reserve = reserve.unwrap_or_default();
+        let candle = candle.unwrap_or_default();
+
         require!(title.chars().count() <= 50, Err(AuctionError::TitleOverflow.into()));
         require!(increment != 0, Err(AuctionError::InvalidIncrement.into()));
         require!(token_amount != 0, Err(AuctionError::InvalidTokenAmount.into()));
@@ -44,6 +56,46 @@ pub mod auctionhouse {
         require!(cur_time > start_time || start_time == 0, Err(AuctionError::InvalidStartTime.into()));
         require!(cur_time < end_time, Err(AuctionError::InvalidEndTime.into()));
         require!(floor > 0, Err(AuctionError::InvalidBidFloor.into()));
+        require!(
+            soft_close.window == 0 || soft_close.length > 0,
+            Err(AuctionError::InvalidExtension.into())
+        );
+        require!(
+            buy_now.price == 0 || buy_now.price > floor,
+            Err(AuctionError::InvalidBuyNowPrice.into())
+        );
+        require!(
+            buy_now.price == 0 || (buy_now.cutoff_bps > 0 && buy_now.cutoff_bps <= BPS_DENOMINATOR),
+            Err(AuctionError::InvalidBuyNowCutoff.into())
+        );
+        require!(
+            reserve.hash == [0u8; 32] || reserve.reveal_window > 0,
+            Err(AuctionError::InvalidReserveRevealWindow.into())
+        );
+        require!(
+            candle.window == 0 ||
+            (candle.window < end_time - cur_time && candle.log_cap > 0),
+            Err(AuctionError::InvalidCandleWindow.into())
+        );
+        require!(
+            candle.window == 0 || (soft_close.window == 0 && buy_now.price == 0),
+            Err(AuctionError::CandleIncompatible.into())
+        );
+        // the candle draw already picks the winner from the logged leads
+        require!(
+            candle.window == 0 || tie_break != TieBreak::Random,
+            Err(AuctionError::RandomTieBreakWithCandle.into())
+        );
+        require!(
+            units > 0 && token_amount % units == 0,
+            Err(AuctionError::InvalidUnits.into())
+        );
+        require!(
+            units == 1 ||
+            (!proxy_bidding && buy_now.price == 0 && reserve.hash == [0u8; 32] &&
+            candle.window == 0 && bundle_amounts.is_empty()),
+            Err(AuctionError::MultiUnitIncompatible.into())
+        );
 
         auction.owner = *owner.key;
         auction.mint = mint.key();
@@ -53,12 +105,40 @@ pub mod auctionhouse {
         auction.end_time = end_time;
         auction.cancelled = false;
 
+        auction.extension_window = soft_close.window;
+        auction.extension_length = soft_close.length;
+        auction.max_extensions = soft_close.max_extensions;
+        auction.extensions = 0;
+
+        auction.candle_window = candle.window;
+        auction.candle_log_cap = candle.log_cap;
+        auction.candle_draw_slot = 0;
+        auction.candle_cutoff = 0;
+        auction.candle_resolved = false;
+
         auction.title = title;
 
         auction.bidder_cap = bidder_cap;
+        auction.tie_break = tie_break;
+        auction.tie_draw_slot = 0;
+        auction.ties_drawn = false;
         auction.highest_bid = 0;
         auction.bid_floor = floor;
         auction.min_bid_increment = increment;
+        auction.proxy_bidding = proxy_bidding;
+        auction.winning_bid_withdrawn = false;
+
+        auction.buy_now_price = buy_now.price;
+        auction.buy_now_cutoff_bps = buy_now.cutoff_bps;
+        auction.item_withdrawn = false;
+
+        auction.reserve_hash = reserve.hash;
+        auction.reserve_reveal_window = reserve.reveal_window;
+        auction.reserve_price = 0;
+        auction.reserve_revealed = false;
+
+        auction.units = units;
+        auction.unsold_reclaimed = false;
 
         auction.bump = bump;
 
@@ -82,6 +162,18 @@ pub mod auctionhouse {
             &[]
         )?;
 
+        auction.bundle = escrow_bundle(
+            owner.to_account_info(),
+            auction.to_account_info(),
+            &auction.mint,
+            &bundle_amounts,
+            ctx.remaining_accounts,
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info()
+        )?;
+
         Ok(())
     }
 
@@ -131,20 +223,94 @@ pub mod auctionhouse {
             total_bid > auction.bid_floor,
             Err(AuctionError::UnderBidFloor.into())
         );
-        require!(
-            total_bid > (auction.highest_bid + auction.min_bid_increment),
-            Err(AuctionError::InsufficientBid.into())
-        );
+
+        // with proxy bidding the leader can raise their maximum without moving the price
+        let raising_proxy = auction.proxy_bidding
+            && auction.highest_bid > 0
+            && *bidder.key == auction.highest_bidder;
+
+        // with several units the bid to beat is the lowest one still winning
+        if !raising_proxy {
+            require!(
+                total_bid > (auction.lowest_winning_bid() + auction.min_bid_increment),
+                Err(AuctionError::InsufficientBid.into())
+            );
+        }
+
+        // look up the leader's maximum before the bidder vectors change
+        let (leader_max, leader_tie_key) = if auction.highest_bid == 0 {
+            (auction.bid_floor, u64::MAX)
+        } else {
+            let leader = auction.bidders.iter().position(|&x| x == auction.highest_bidder);
+            (auction.bids[leader.unwrap()], auction.tie_keys[leader.unwrap()])
+        };
+
+        // an open bid is committed and revealed at once
+        let tie_key = tie_break_key(auction.tie_break, cur_time, cur_time);
 
         if new_bidder {
             auction.bidders.push(*bidder.key);
             auction.bids.push(total_bid);
-        } else {
+            auction.tie_keys.push(tie_key);
+        } else if amount > 0 {
             auction.bids[index.unwrap()] = total_bid;
+            auction.tie_keys[index.unwrap()] = tie_key;
         }
 
-        auction.highest_bidder = *bidder.key;
-        auction.highest_bid = total_bid;
+        let prev_highest_bidder = auction.highest_bidder;
+        let prev_highest_bid = auction.highest_bid;
+
+        if !auction.proxy_bidding {
+            if total_bid > auction.highest_bid {
+                auction.highest_bidder = *bidder.key;
+                auction.highest_bid = total_bid;
+            }
+        } else if !raising_proxy {
+            // the higher maximum leads at the lower maximum plus the increment,
+            // and equal maximums go by the tie-break policy
+            if total_bid > leader_max || (total_bid == leader_max && tie_key < leader_tie_key) {
+                auction.highest_bidder = *bidder.key;
+                auction.highest_bid = std::cmp::min(total_bid, leader_max + auction.min_bid_increment);
+            } else {
+                auction.highest_bid = std::cmp::min(leader_max, total_bid + auction.min_bid_increment);
+            }
+        }
+
+        if auction.candle_window > 0 && cur_time >= auction.candle_window_start() {
+            // the lead held when the window opened is the fallback if the close lands before any logged bid
+            if auction.candle_log.is_empty() && prev_highest_bid > 0 {
+                let fallback = CandleBid {
+                    bidder: prev_highest_bidder,
+                    bid: prev_highest_bid,
+                    time: auction.candle_window_start(),
+                };
+                auction.candle_log.push(fallback);
+            }
+
+            let lead = CandleBid {
+                bidder: auction.highest_bidder,
+                bid: auction.highest_bid,
+                time: cur_time,
+            };
+            let bucket = auction.candle_bucket(cur_time);
+            let last_bucket = auction.candle_log.last().map(|entry| auction.candle_bucket(entry.time));
+
+            // only lead changes are logged, and one per bucket at most, so the log never fills up
+            match auction.candle_log.last_mut() {
+                Some(last) if last.bidder == lead.bidder => last.bid = lead.bid,
+                Some(last) if last_bucket == Some(bucket) => *last = lead,
+                _ => auction.candle_log.push(lead),
+            }
+        }
+
+        // soft close, end_time is the source of truth for every later phase check
+        if auction.extension_window > 0
+            && auction.end_time - cur_time <= auction.extension_window
+            && (auction.max_extensions == 0 || auction.extensions < auction.max_extensions)
+        {
+            auction.end_time += auction.extension_length;
+            auction.extensions += 1;
+        }
 
         transfer_sol(
             bidder.to_account_info(),
@@ -156,21 +322,150 @@ pub mod auctionhouse {
         Ok(())
     }
 
+    pub fn buy_now_open<'info>(ctx: Context<'_, '_, '_, 'info, BuyNowOpen<'info>>) -> ProgramResult {
+        let auction: &mut Account<OpenAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let buyer = &ctx.accounts.buyer;
+        let buyer_ata = &ctx.accounts.buyer_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.start_time, Err(AuctionError::BidBeforeStart.into()));
+        require!(cur_time < auction.end_time, Err(AuctionError::BidAfterClose.into()));
+        require!(*buyer.key != auction.owner, Err(AuctionError::OwnerCannotBid.into()));
+        require!(
+            auction.buy_now_price > 0 &&
+            (auction.highest_bid as u128) * (BPS_DENOMINATOR as u128) <=
+            (auction.buy_now_price as u128) * (auction.buy_now_cutoff_bps as u128),
+            Err(AuctionError::BuyNowUnavailable.into())
+        );
+
+        let price = auction.buy_now_price;
+        let index = auction.bidders.iter().position(|&x| x == *buyer.key);
+
+        // an existing bid counts towards the buy-now price and a larger proxy maximum is refunded
+        let top_up;
+        let excess;
+        if let Some(i) = index {
+            top_up = price.saturating_sub(auction.bids[i]);
+            excess = auction.bids[i].saturating_sub(price);
+            auction.bids[i] = price;
+        } else {
+            require!(
+                auction.bidders.len() < (auction.bidder_cap as usize),
+                Err(AuctionError::BidderCapReached.into())
+            );
+            top_up = price;
+            excess = 0;
+            auction.bidders.push(*buyer.key);
+            auction.bids.push(price);
+            // the sale closes the auction, so this key never breaks a tie
+            auction.tie_keys.push(cur_time);
+        }
+
+        // close the auction now so the seller can withdraw and the other bidders can reclaim
+        // the seller chose the buy-now price, so it waives any hidden reserve
+        auction.highest_bidder = *buyer.key;
+        auction.highest_bid = price;
+        auction.end_time = cur_time;
+        auction.item_withdrawn = true;
+        auction.reserve_hash = [0u8; 32];
+
+        let amount = auction.token_amount;
+
+        transfer_sol(
+            buyer.to_account_info(),
+            auction.to_account_info(),
+            top_up,
+            system_program.to_account_info()
+        )?;
+
+        if buyer_ata.to_account_info().data_is_empty() {
+            create_ata(
+                buyer.to_account_info(),
+                buyer.to_account_info(),
+                mint.to_account_info(),
+                buyer_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            buyer_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"open auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        // item_withdrawn is set above, so the bundle has to leave with the lot
+        release_bundle(
+            buyer.to_account_info(),
+            auction.to_account_info(),
+            &auction.bundle,
+            ctx.remaining_accounts,
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info(),
+            &[&[b"open auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        // must happen after the cpis, see withdraw_item_sealed
+        if excess > 0 {
+            let src = &mut auction.to_account_info();
+            let dst = &mut buyer.to_account_info();
+            transfer_from_owned_account(src, dst, excess)?;
+        }
+
+        Ok(())
+    }
+
     pub fn reclaim_open_bid(ctx: Context<ReclaimOpenBid>) -> ProgramResult {
         let auction: &mut Account<OpenAuction> = &mut ctx.accounts.auction;
         let bidder: &Signer = &ctx.accounts.bidder;
 
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
         let index = auction.bidders.iter().position(|&x| x == *bidder.key);
+        let reserve_not_met = auction.reserve_status(cur_time) == ReserveStatus::NotMet;
+        let candle_locked = auction.candle_window > 0
+            && !auction.candle_resolved
+            && !auction.cancelled
+            && auction.candle_log.iter().any(|entry| entry.bidder == *bidder.key);
+        // once bidding is over a bid tied across the last winning place may still win the draw
+        let tie_locked = cur_time > auction.end_time
+            && !auction.cancelled
+            && !reserve_not_met
+            && auction.tie_pending()
+            && index.map(|i| auction.bids[i]) == auction.boundary_tie();
 
         if let None = index {
             return Err(AuctionError::NotBidder.into())
-        } else if *bidder.key == auction.highest_bidder && !auction.cancelled {
+        } else if auction.is_winner(bidder.key) && !auction.cancelled && !reserve_not_met {
             return Err(AuctionError::WinnerCannotWithdrawBid.into())
+        } else if candle_locked {
+            return Err(AuctionError::CandleBidLocked.into())
+        } else if tie_locked {
+            return Err(AuctionError::TieNotDrawn.into())
         } else {
             let bid = auction.bids[index.unwrap()];
 
             auction.bidders.remove(index.unwrap());
             auction.bids.remove(index.unwrap());
+            auction.tie_keys.remove(index.unwrap());
 
             let src = &mut auction.to_account_info();
             let dst = &mut bidder.to_account_info();
@@ -181,7 +476,172 @@ pub mod auctionhouse {
         Ok(())
     }
 
-    pub fn withdraw_item_open(ctx: Context<WithdrawItemOpen>) -> ProgramResult {
+    // same commit-then-draw flow as the raffle, so whoever resolves can't pick a favorable hash
+    pub fn commit_candle_draw_open(ctx: Context<CommitCandleDrawOpen>) -> ProgramResult {
+        let auction: &mut Account<OpenAuction> = &mut ctx.accounts.auction;
+        let slot_hashes = &ctx.accounts.slot_hashes;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(auction.candle_window > 0, Err(AuctionError::NotCandleAuction.into()));
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
+        require!(!auction.candle_resolved, Err(AuctionError::CandleAlreadyResolved.into()));
+
+        // only recommit once the old draw slot was skipped or aged out, see commit_raffle_draw
+        if auction.candle_draw_slot != 0 {
+            require!(
+                clock.slot > auction.candle_draw_slot
+                    && slot_hash_at(slot_hashes, auction.candle_draw_slot)?.is_none(),
+                Err(AuctionError::DrawSlotPending.into())
+            );
+        }
+
+        auction.candle_draw_slot = clock.slot + DRAW_DELAY;
+
+        Ok(())
+    }
+
+    pub fn resolve_candle_open(ctx: Context<ResolveCandleOpen>) -> ProgramResult {
+        let auction: &mut Account<OpenAuction> = &mut ctx.accounts.auction;
+        let slot_hashes = &ctx.accounts.slot_hashes;
+
+        let clock: Clock = Clock::get().unwrap();
+
+        require!(auction.candle_window > 0, Err(AuctionError::NotCandleAuction.into()));
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(!auction.candle_resolved, Err(AuctionError::CandleAlreadyResolved.into()));
+        require!(auction.candle_draw_slot != 0, Err(AuctionError::DrawNotCommitted.into()));
+        require!(clock.slot > auction.candle_draw_slot, Err(AuctionError::DrawSlotPending.into()));
+
+        let slot_hash = slot_hash_at(slot_hashes, auction.candle_draw_slot)?
+            .ok_or(AuctionError::DrawSlotExpired)?;
+        let seed = random_u64(&slot_hash, &auction.key());
+        // the candle burns out at the end of a random bucket, the granularity the log was kept at
+        let bucket_length = auction.candle_bucket_length();
+        let buckets = (auction.candle_window + bucket_length - 1) / bucket_length;
+        let cutoff = std::cmp::min(
+            auction.candle_window_start() + (seed % buckets + 1) * bucket_length - 1,
+            auction.end_time - 1
+        );
+
+        auction.candle_cutoff = cutoff;
+        auction.candle_resolved = true;
+
+        // with no bids in the window the lead at end_time already stands
+        if !auction.candle_log.is_empty() {
+            let winner = auction.candle_log.iter().rev().find(|entry| entry.time <= cutoff).copied();
+            match winner {
+                Some(entry) => {
+                    auction.highest_bidder = entry.bidder;
+                    auction.highest_bid = entry.bid;
+                }
+                None => {
+                    auction.highest_bidder = Pubkey::default();
+                    auction.highest_bid = 0;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // equal bids only get random keys from a slot committed once bidding is over, see commit_raffle_draw
+    pub fn commit_tie_break_draw_open(ctx: Context<CommitTieBreakDrawOpen>) -> ProgramResult {
+        let auction: &mut Account<OpenAuction> = &mut ctx.accounts.auction;
+        let slot_hashes = &ctx.accounts.slot_hashes;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
+        require!(auction.tie_pending(), Err(AuctionError::NoTieToDraw.into()));
+
+        if auction.tie_draw_slot != 0 {
+            require!(
+                clock.slot > auction.tie_draw_slot
+                    && slot_hash_at(slot_hashes, auction.tie_draw_slot)?.is_none(),
+                Err(AuctionError::DrawSlotPending.into())
+            );
+        }
+
+        auction.tie_draw_slot = clock.slot + DRAW_DELAY;
+
+        Ok(())
+    }
+
+    pub fn draw_tie_break_open(ctx: Context<DrawTieBreakOpen>) -> ProgramResult {
+        let auction: &mut Account<OpenAuction> = &mut ctx.accounts.auction;
+        let slot_hashes = &ctx.accounts.slot_hashes;
+
+        let clock: Clock = Clock::get().unwrap();
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(auction.tie_draw_slot != 0, Err(AuctionError::DrawNotCommitted.into()));
+        require!(clock.slot > auction.tie_draw_slot, Err(AuctionError::DrawSlotPending.into()));
+
+        require!(auction.tie_pending(), Err(AuctionError::NoTieToDraw.into()));
+        let tied_bid = auction.boundary_tie().unwrap();
+
+        let slot_hash = slot_hash_at(slot_hashes, auction.tie_draw_slot)?
+            .ok_or(AuctionError::DrawSlotExpired)?;
+
+        for i in 0..auction.bids.len() {
+            if auction.bids[i] == tied_bid {
+                auction.tie_keys[i] = random_u64(&slot_hash, &auction.bidders[i]);
+            }
+        }
+        auction.ties_drawn = true;
+
+        // equal proxy maximums leave the price alone, only the leader changes
+        if auction.units == 1 {
+            let winner = auction.winning_indices()[0];
+            auction.highest_bidder = auction.bidders[winner];
+        }
+
+        Ok(())
+    }
+
+    pub fn reveal_reserve_open(ctx: Context<RevealReserveOpen>, reserve: u64, nonce: u64) -> ProgramResult {
+        let auction: &mut Account<OpenAuction> = &mut ctx.accounts.auction;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(auction.has_reserve(), Err(AuctionError::NoReserve.into()));
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
+        require!(!auction.reserve_revealed, Err(AuctionError::ReserveAlreadyRevealed.into()));
+        require!(
+            auction.candle_window == 0 || auction.candle_resolved,
+            Err(AuctionError::CandleNotResolved.into())
+        );
+        require!(
+            cur_time <= auction.end_time + auction.reserve_reveal_window,
+            Err(AuctionError::ReserveRevealWindowOver.into())
+        );
+        require!(
+            auction.reserve_hash == compute_reserve_hash(reserve, nonce),
+            Err(AuctionError::ReserveHashMismatch.into())
+        );
+
+        auction.reserve_price = reserve;
+        auction.reserve_revealed = true;
+
+        // a proxy maximum at or above the reserve bids up to meet it
+        if auction.proxy_bidding && auction.highest_bid > 0 && reserve > auction.highest_bid {
+            let leader = auction.bidders.iter().position(|&x| x == auction.highest_bidder);
+            if auction.bids[leader.unwrap()] >= reserve {
+                auction.highest_bid = reserve;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn withdraw_item_open<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawItemOpen<'info>>) -> ProgramResult {
         let auction: &mut Account<OpenAuction> = &mut ctx.accounts.auction;
         let auction_ata = &ctx.accounts.auction_ata;
         let winner = &ctx.accounts.highest_bidder;
@@ -197,6 +657,20 @@ pub mod auctionhouse {
 
         require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
         require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
+        require!(!auction.item_withdrawn, Err(AuctionError::ItemAlreadyWithdrawn.into()));
+        require!(auction.units == 1, Err(AuctionError::WrongUnitWithdraw.into()));
+        require!(
+            auction.candle_window == 0 || auction.candle_resolved,
+            Err(AuctionError::CandleNotResolved.into())
+        );
+        require!(!auction.tie_pending(), Err(AuctionError::TieNotDrawn.into()));
+        match auction.reserve_status(cur_time) {
+            ReserveStatus::Pending => return Err(AuctionError::ReservePending.into()),
+            ReserveStatus::NotMet => return Err(AuctionError::ReserveNotMet.into()),
+            ReserveStatus::Met => {}
+        }
+
+        auction.item_withdrawn = true;
 
         let amount = auction.token_amount;
 
@@ -222,43 +696,40 @@ pub mod auctionhouse {
             &[&[b"open auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
         )?;
 
-        Ok(())
-    }
-
-    pub fn withdraw_winning_bid_open(ctx: Context<WithdrawWinningBidOpen>) -> ProgramResult {
-        let auction: &mut Account<OpenAuction> = &mut ctx.accounts.auction;
-        let owner: &Signer = &ctx.accounts.owner;
-
-        let clock: Clock = Clock::get().unwrap();
-        let cur_time: u64 = clock.unix_timestamp as u64;
-
-        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
-        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
-
-        let index = auction.bidders.iter().position(|&x| x == auction.highest_bidder);
-        if let None = index {
-            return Err(AuctionError::NoWinningBid.into())
-        } else {
-            let winning_bid = auction.bids[index.unwrap()];
+        release_bundle(
+            winner.to_account_info(),
+            auction.to_account_info(),
+            &auction.bundle,
+            ctx.remaining_accounts,
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info(),
+            &[&[b"open auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
 
-            require!(winning_bid > 0, Err(AuctionError::AlreadyWithdrewBid.into()));
+        // refund escrow above the winning price, which is only nonzero for proxy bids
+        // must happen last for the same reason as in withdraw_item_sealed
+        let index = auction.bidders.iter().position(|&x| x == *winner.key);
+        let owed = if auction.winning_bid_withdrawn { 0 } else { auction.highest_bid };
+        let excess = auction.bids[index.unwrap()] - owed;
 
-            auction.bids[index.unwrap()] = 0;
+        if excess > 0 {
+            auction.bids[index.unwrap()] -= excess;
 
             let src = &mut auction.to_account_info();
-            let dst = &mut owner.to_account_info();
-
-            transfer_from_owned_account(src, dst, winning_bid)?;
+            let dst = &mut winner.to_account_info();
+            transfer_from_owned_account(src, dst, excess)?;
         }
 
         Ok(())
     }
 
-    pub fn reclaim_item_open(ctx: Context<ReclaimItemOpen>) -> ProgramResult {
+    pub fn withdraw_unit_open(ctx: Context<WithdrawUnitOpen>) -> ProgramResult {
         let auction: &mut Account<OpenAuction> = &mut ctx.accounts.auction;
         let auction_ata = &ctx.accounts.auction_ata;
-        let owner = &ctx.accounts.owner;
-        let owner_ata = &ctx.accounts.owner_ata;
+        let bidder = &ctx.accounts.bidder;
+        let bidder_ata = &ctx.accounts.bidder_ata;
         let mint = &ctx.accounts.mint;
         let token_program = &ctx.accounts.token_program;
         let ata_program = &ctx.accounts.ata_program;
@@ -268,19 +739,26 @@ pub mod auctionhouse {
         let clock: Clock = Clock::get().unwrap();
         let cur_time: u64 = clock.unix_timestamp as u64;
 
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
+        require!(auction.units > 1, Err(AuctionError::WrongUnitWithdraw.into()));
+        require!(!auction.tie_pending(), Err(AuctionError::TieNotDrawn.into()));
+        require!(auction.is_winner(bidder.key), Err(AuctionError::NotWinningBidder.into()));
         require!(
-            (auction.highest_bid == 0 && cur_time > auction.end_time) || auction.cancelled,
-            Err(AuctionError::AuctionNotOver.into())
+            !auction.unit_claims.contains(bidder.key),
+            Err(AuctionError::ItemAlreadyWithdrawn.into())
         );
 
-        let amount = auction.token_amount;
+        auction.unit_claims.push(*bidder.key);
 
-        if owner_ata.to_account_info().data_is_empty() {
+        let amount = auction.unit_amount();
+
+        if bidder_ata.to_account_info().data_is_empty() {
             create_ata(
-                owner.to_account_info(),
-                owner.to_account_info(),
+                bidder.to_account_info(),
+                bidder.to_account_info(),
                 mint.to_account_info(),
-                owner_ata.to_account_info(),
+                bidder_ata.to_account_info(),
                 token_program.to_account_info(),
                 ata_program.to_account_info(),
                 system_program.to_account_info(),
@@ -291,7 +769,7 @@ pub mod auctionhouse {
         transfer_spl(
             auction.to_account_info(),
             auction_ata.to_account_info(),
-            owner_ata.to_account_info(),
+            bidder_ata.to_account_info(),
             amount,
             token_program.to_account_info(),
             &[&[b"open auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
@@ -300,10 +778,148 @@ pub mod auctionhouse {
         Ok(())
     }
 
-    pub fn create_sealed_auction(
-        ctx: Context<CreateSealedAuction>,
-        bump: u8,
-        title: String,
+    pub fn withdraw_winning_bid_open(ctx: Context<WithdrawWinningBidOpen>) -> ProgramResult {
+        let auction: &mut Account<OpenAuction> = &mut ctx.accounts.auction;
+        let owner: &Signer = &ctx.accounts.owner;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        // a buy-now ends the auction in the same second it was bought
+        require!(
+            cur_time > auction.end_time || auction.item_withdrawn,
+            Err(AuctionError::AuctionNotOver.into())
+        );
+        require!(
+            auction.candle_window == 0 || auction.candle_resolved,
+            Err(AuctionError::CandleNotResolved.into())
+        );
+        require!(!auction.tie_pending(), Err(AuctionError::TieNotDrawn.into()));
+        match auction.reserve_status(cur_time) {
+            ReserveStatus::Pending => return Err(AuctionError::ReservePending.into()),
+            ReserveStatus::NotMet => return Err(AuctionError::ReserveNotMet.into()),
+            ReserveStatus::Met => {}
+        }
+
+        // every multi-unit winner pays their own bid, which stays escrowed since winners can't reclaim
+        if auction.units > 1 {
+            require!(
+                !auction.winning_bid_withdrawn,
+                Err(AuctionError::AlreadyWithdrewBid.into())
+            );
+
+            let proceeds: u64 = auction.winning_indices().iter().map(|&i| auction.bids[i]).sum();
+            require!(proceeds > 0, Err(AuctionError::NoWinningBid.into()));
+
+            auction.winning_bid_withdrawn = true;
+
+            let src = &mut auction.to_account_info();
+            let dst = &mut owner.to_account_info();
+
+            transfer_from_owned_account(src, dst, proceeds)?;
+
+            return Ok(());
+        }
+
+        let index = auction.bidders.iter().position(|&x| x == auction.highest_bidder);
+        if let None = index {
+            return Err(AuctionError::NoWinningBid.into())
+        } else {
+            let winning_bid = auction.highest_bid;
+
+            require!(
+                !auction.winning_bid_withdrawn,
+                Err(AuctionError::AlreadyWithdrewBid.into())
+            );
+
+            auction.winning_bid_withdrawn = true;
+            auction.bids[index.unwrap()] -= winning_bid;
+
+            let src = &mut auction.to_account_info();
+            let dst = &mut owner.to_account_info();
+
+            transfer_from_owned_account(src, dst, winning_bid)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn reclaim_item_open<'info>(ctx: Context<'_, '_, '_, 'info, ReclaimItemOpen<'info>>) -> ProgramResult {
+        let auction: &mut Account<OpenAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let owner_ata = &ctx.accounts.owner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        // a multi-unit auction only returns the units nobody won
+        let amount = if auction.units > 1 && !auction.cancelled {
+            require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
+            require!(!auction.unsold_reclaimed, Err(AuctionError::ItemAlreadyReclaimed.into()));
+
+            let unsold = auction.units - auction.winning_indices().len() as u64;
+            require!(unsold > 0, Err(AuctionError::NothingToWithdraw.into()));
+
+            auction.unsold_reclaimed = true;
+            unsold * auction.unit_amount()
+        } else {
+            require!(
+                (auction.highest_bid == 0 && cur_time > auction.end_time) ||
+                auction.reserve_status(cur_time) == ReserveStatus::NotMet ||
+                auction.cancelled,
+                Err(AuctionError::AuctionNotOver.into())
+            );
+            auction.token_amount
+        };
+
+        if owner_ata.to_account_info().data_is_empty() {
+            create_ata(
+                owner.to_account_info(),
+                owner.to_account_info(),
+                mint.to_account_info(),
+                owner_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            owner_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"open auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        release_bundle(
+            owner.to_account_info(),
+            auction.to_account_info(),
+            &auction.bundle,
+            ctx.remaining_accounts,
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info(),
+            &[&[b"open auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn create_sealed_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreateSealedAuction<'info>>,
+        bump: u8,
+        title: String,
         floor: u64,
         first_price: bool,
         start_time: u64,
@@ -311,6 +927,7 @@ pub mod auctionhouse {
         reveal_period: u64,
         bidder_cap: u64,
         token_amount: u64,
+        options: SealedAuctionOptions,
     ) -> ProgramResult {
         let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
         let auction_ata = &ctx.accounts.auction_ata;
@@ -325,6 +942,14 @@ pub mod auctionhouse {
         let clock: Clock = Clock::get().unwrap();
         let cur_time: u64 = clock.unix_timestamp as u64;
 
+        let SealedAuctionOptions {
+            bundle_amounts, commitment_version, penalty, deposits, auctioneer, tie_break
+        } = options;
+        // a config left as None is off, the same as an all-zero one
+        let penalty = penalty.unwrap_or_default();
+        let deposits = deposits.unwrap_or_default();
+        let auctioneer = auctioneer.unwrap_or_default();
+
         require!(title.chars().count() <= 50, Err(AuctionError::TitleOverflow.into()));
         require!(token_amount != 0, Err(AuctionError::InvalidTokenAmount.into()));
         require!(start_time < end_time, Err(AuctionError::InvalidStartTime.into()));
@@ -332,8 +957,37 @@ pub mod auctionhouse {
         require!(cur_time < end_time, Err(AuctionError::InvalidEndTime.into()));
         require!(reveal_period > end_time, Err(AuctionError::InvalidRevealPeriod.into()));
         require!(floor > 0, Err(AuctionError::InvalidBidFloor.into()));
+        require!(
+            commitment_version == COMMITMENT_V1 || commitment_version == COMMITMENT_V2,
+            Err(AuctionError::InvalidCommitmentVersion.into())
+        );
+        require!(
+            penalty.bps <= BPS_DENOMINATOR && (penalty.bps == 0 || penalty.bond == 0),
+            Err(AuctionError::InvalidPenalty.into())
+        );
+        // forfeits could never be withdrawn otherwise
+        require!(
+            (penalty.bps == 0 && penalty.bond == 0) || penalty.recipient != Pubkey::default(),
+            Err(AuctionError::MissingPenaltyRecipient.into())
+        );
+        // a keeper can only open commitments that bind the bid to the bidder
+        require!(
+            auctioneer == Pubkey::default() || commitment_version == COMMITMENT_V2,
+            Err(AuctionError::AuctioneerRequiresV2.into())
+        );
+        // a single tier makes every deposit identical
+        require!(
+            deposits.tiers.len() <= MAX_DEPOSIT_TIERS
+                && deposits.tiers.windows(2).all(|w| w[0] < w[1])
+                && deposits.tiers.first().map_or(true, |&tier| tier > floor && tier >= penalty.bond),
+            Err(AuctionError::InvalidDepositTiers.into())
+        );
 
         auction.first_price = first_price;
+        auction.commitment_version = commitment_version;
+        auction.tie_break = tie_break;
+        auction.tie_draw_slot = 0;
+        auction.ties_drawn = false;
 
         auction.owner = *owner.key;
         auction.mint = mint.key();
@@ -348,12 +1002,25 @@ pub mod auctionhouse {
 
         auction.bidder_cap = bidder_cap;
         auction.highest_bid = 0;
-        auction.second_highest_bid = 0;
         auction.bid_floor = floor;
         auction.winning_bid_withdrawn = false;
+        auction.item_withdrawn = false;
+
+        auction.allow_deposit_decrease = deposits.allow_decrease;
+
+        auction.auctioneer = auctioneer;
+
+        auction.deposit_tiers = deposits.tiers;
+
+        auction.penalty_bps = penalty.bps;
+        auction.penalty_bond = penalty.bond;
+        auction.penalty_recipient = penalty.recipient;
+        auction.forfeited = 0;
 
         auction.bump = bump;
 
+        auction.transition(cur_time);
+
         create_ata(
             owner.to_account_info(),
             auction.to_account_info(),
@@ -374,6 +1041,18 @@ pub mod auctionhouse {
             &[]
         )?;
 
+        auction.bundle = escrow_bundle(
+            owner.to_account_info(),
+            auction.to_account_info(),
+            &auction.mint,
+            &bundle_amounts,
+            ctx.remaining_accounts,
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info()
+        )?;
+
         Ok(())
     }
 
@@ -383,14 +1062,20 @@ pub mod auctionhouse {
         let clock: Clock = Clock::get().unwrap();
         let cur_time: u64 = clock.unix_timestamp as u64;
 
-        require!(cur_time < auction.end_time, Err(AuctionError::CannotCancelRevealPeriod.into()));
+        auction.require_phase(cur_time, &[Phase::Scheduled, Phase::Bidding])?;
 
         auction.cancelled = true;
+        auction.transition(cur_time);
 
         Ok(())
     }
 
-    pub fn make_sealed_bid(ctx: Context<MakeSealedBid>, bid_hash: [u8; 32], amount: u64) -> ProgramResult {
+    pub fn make_sealed_bid(
+        ctx: Context<MakeSealedBid>,
+        bid_hash: [u8; 32],
+        amount: u64,
+        ciphertext: Vec<u8>
+    ) -> ProgramResult {
         let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
         let bidder: &Signer = &ctx.accounts.bidder;
         let system_program = &ctx.accounts.system_program;
@@ -398,11 +1083,12 @@ pub mod auctionhouse {
         let clock: Clock = Clock::get().unwrap();
         let cur_time: u64 = clock.unix_timestamp as u64;
 
-        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
-        require!(cur_time > auction.start_time, Err(AuctionError::BidBeforeStart.into()));
-        require!(cur_time < auction.end_time, Err(AuctionError::BidAfterClose.into()));
+        auction.require_phase(cur_time, &[Phase::Bidding])?;
         require!(*bidder.key != auction.owner, Err(AuctionError::OwnerCannotBid.into()));
         require!(amount > 0, Err(AuctionError::MustSendSol.into()));
+        require!(amount >= auction.penalty_bond, Err(AuctionError::UnderPenaltyBond.into()));
+        require!(auction.accepts_deposit(amount), Err(AuctionError::NotADepositTier.into()));
+        require!(auction.accepts_ciphertext(&ciphertext), Err(AuctionError::InvalidCiphertext.into()));
 
         let index = auction.bidders.iter().position(|&x| x == *bidder.key);
 
@@ -414,6 +1100,9 @@ pub mod auctionhouse {
             auction.bidders.push(*bidder.key);
             auction.sealed_bids.push(bid_hash);
             auction.fake_bids.push(amount);
+            auction.revealed.push(false);
+            auction.commit_times.push(cur_time);
+            auction.ciphertexts.push(ciphertext);
         } else {
             return Err(AuctionError::DuplicateSealedBid.into());
         }
@@ -428,40 +1117,72 @@ pub mod auctionhouse {
         Ok(())
     }
 
-    pub fn reclaim_sealed_bid(ctx: Context<ReclaimSealedBid>) -> ProgramResult {
+    // swaps the commitment and moves the deposit to amount without giving up the bidder's slot
+    pub fn update_sealed_bid(
+        ctx: Context<UpdateSealedBid>,
+        bid_hash: [u8; 32],
+        amount: u64,
+        ciphertext: Vec<u8>
+    ) -> ProgramResult {
         let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
         let bidder: &Signer = &ctx.accounts.bidder;
+        let system_program = &ctx.accounts.system_program;
 
-        let index = auction.bidders.iter().position(|&x| x == *bidder.key);
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
 
-        if let None = index {
-            return Err(AuctionError::NotBidder.into())
-        } else {
-            let fake_bid = auction.fake_bids[index.unwrap()];
+        auction.require_phase(cur_time, &[Phase::Bidding])?;
+        require!(amount > 0, Err(AuctionError::MustSendSol.into()));
+        require!(amount >= auction.penalty_bond, Err(AuctionError::UnderPenaltyBond.into()));
+        require!(auction.accepts_deposit(amount), Err(AuctionError::NotADepositTier.into()));
+        require!(auction.accepts_ciphertext(&ciphertext), Err(AuctionError::InvalidCiphertext.into()));
 
-            auction.bidders.remove(index.unwrap());
-            auction.sealed_bids.remove(index.unwrap());
-            auction.fake_bids.remove(index.unwrap());
+        let index = auction.bidders.iter().position(|&x| x == *bidder.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        let fake_bid = auction.fake_bids[index];
+        require!(
+            amount >= fake_bid || auction.allow_deposit_decrease,
+            Err(AuctionError::DepositDecreaseDisallowed.into())
+        );
+
+        auction.sealed_bids[index] = bid_hash;
+        auction.fake_bids[index] = amount;
+        auction.commit_times[index] = cur_time;
+        auction.ciphertexts[index] = ciphertext;
+
+        if amount > fake_bid {
+            transfer_sol(
+                bidder.to_account_info(),
+                auction.to_account_info(),
+                amount - fake_bid,
+                system_program.to_account_info()
+            )?;
+        }
 
+        // must happen after the cpis, see withdraw_item_sealed
+        if amount < fake_bid {
             let src = &mut auction.to_account_info();
             let dst = &mut bidder.to_account_info();
 
-            transfer_from_owned_account(src, dst, fake_bid)?;
+            transfer_from_owned_account(src, dst, fake_bid - amount)?;
         }
 
         Ok(())
     }
 
-    pub fn reveal_sealed_bid(ctx: Context<RevealSealedBid>, bid: u64, nonce: u64) -> ProgramResult {
+    pub fn reclaim_sealed_bid(ctx: Context<ReclaimSealedBid>) -> ProgramResult {
         let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
         let bidder: &Signer = &ctx.accounts.bidder;
 
         let clock: Clock = Clock::get().unwrap();
         let cur_time: u64 = clock.unix_timestamp as u64;
 
-        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
-        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
-        require!(cur_time < auction.reveal_period, Err(AuctionError::RevealPeriodOver.into()));
+        auction.require_phase(
+            cur_time,
+            &[Phase::Bidding, Phase::Revealing, Phase::Settling, Phase::Settled, Phase::Cancelled]
+        )?;
+        let phase = auction.phase;
 
         let index = auction.bidders.iter().position(|&x| x == *bidder.key);
 
@@ -470,152 +1191,352 @@ pub mod auctionhouse {
         } else {
             let fake_bid = auction.fake_bids[index.unwrap()];
 
-            let bid_hash = auction.sealed_bids[index.unwrap()];
-            let proposed_hash = compute_bid_hash(bid, nonce);
+            // the current highest bid is the winning bid once reveals start, only a cancel frees it
+            require!(
+                phase == Phase::Bidding || phase == Phase::Cancelled || *bidder.key != auction.highest_bidder,
+                Err(AuctionError::WinnerCannotWithdrawBid.into())
+            );
+            // so is every bid tied with it until the random draw
+            require!(
+                phase == Phase::Cancelled || !auction.tie_pending() || !auction.in_top_tie(bidder.key),
+                Err(AuctionError::TieNotDrawn.into())
+            );
 
-            require!(bid_hash == proposed_hash, Err(AuctionError::HashMismatch.into()));
-            require!(bid > auction.bid_floor, Err(AuctionError::UnderBidFloor.into()));
-            require!(fake_bid >= bid, Err(AuctionError::InsufficientSol.into()));
+            // bids withdrawn before the close or after a cancel never reach the reveal period
+            let penalized = phase != Phase::Bidding
+                && phase != Phase::Cancelled
+                && !auction.revealed[index.unwrap()];
 
-            if bid > auction.highest_bid {
-                auction.second_highest_bid = auction.highest_bid;
-                auction.highest_bidder = *bidder.key;
-                auction.highest_bid = bid;
-            } else {
-                auction.bidders.remove(index.unwrap());
-                auction.sealed_bids.remove(index.unwrap());
-                auction.fake_bids.remove(index.unwrap());
+            require!(
+                !penalized || phase != Phase::Revealing,
+                Err(AuctionError::RevealPending.into())
+            );
 
-                let src = &mut auction.to_account_info();
-                let dst = &mut bidder.to_account_info();
+            let penalty = if penalized { auction.non_reveal_penalty(fake_bid) } else { 0 };
+            auction.forfeited += penalty;
 
-                transfer_from_owned_account(src, dst, fake_bid)?;
-            }
+            auction.bidders.remove(index.unwrap());
+            auction.sealed_bids.remove(index.unwrap());
+            auction.fake_bids.remove(index.unwrap());
+            auction.revealed.remove(index.unwrap());
+            auction.commit_times.remove(index.unwrap());
+            auction.ciphertexts.remove(index.unwrap());
+
+            let src = &mut auction.to_account_info();
+            let dst = &mut bidder.to_account_info();
+
+            transfer_from_owned_account(src, dst, fake_bid - penalty)?;
         }
 
         Ok(())
     }
 
-    pub fn withdraw_item_sealed(ctx: Context<WithdrawItemSealed>) -> ProgramResult {
+    pub fn withdraw_forfeits_sealed(ctx: Context<WithdrawForfeitsSealed>) -> ProgramResult {
         let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
-        let auction_ata = &ctx.accounts.auction_ata;
-        let winner = &ctx.accounts.highest_bidder;
-        let winner_ata = &ctx.accounts.highest_bidder_ata;
-        let mint = &ctx.accounts.mint;
-        let token_program = &ctx.accounts.token_program;
-        let ata_program = &ctx.accounts.ata_program;
-        let system_program = &ctx.accounts.system_program;
-        let rent_sysvar = &ctx.accounts.rent_sysvar;
+        let penalty_recipient: &Signer = &ctx.accounts.penalty_recipient;
 
         let clock: Clock = Clock::get().unwrap();
         let cur_time: u64 = clock.unix_timestamp as u64;
 
-        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
-        require!(cur_time > auction.reveal_period, Err(AuctionError::RevealPeriodNotOver.into()));
-
-        let amount = auction.token_amount;
-
-        if winner_ata.to_account_info().data_is_empty() {
-            create_ata(
-                winner.to_account_info(),
-                winner.to_account_info(),
-                mint.to_account_info(),
-                winner_ata.to_account_info(),
-                token_program.to_account_info(),
-                ata_program.to_account_info(),
-                system_program.to_account_info(),
-                rent_sysvar.to_account_info()
-            )?;
-        }
+        auction.require_phase(cur_time, &[Phase::Settling, Phase::Settled])?;
 
-        transfer_spl(
-            auction.to_account_info(),
-            auction_ata.to_account_info(),
-            winner_ata.to_account_info(),
-            amount,
-            token_program.to_account_info(),
-            &[&[b"sealed auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
-        )?;
+        let amount = auction.forfeited;
+        require!(amount > 0, Err(AuctionError::NothingToWithdraw.into()));
 
-        // refund difference between sent SOL and real bid SOL
-        // must happen last bc solana does a pre-cpi account balance check
-        // so we can't transfer sol before create_ata or solana will think smth is wrong
-        let index = auction.bidders.iter().position(|&x| x == *winner.key);
-        let fake_bid = auction.fake_bids[index.unwrap()];
-        let bid_delta;
-        if auction.first_price || auction.second_highest_bid == 0 {
-            bid_delta = fake_bid - auction.highest_bid;
-        } else {
-            bid_delta = fake_bid - auction.second_highest_bid;
-        }
+        auction.forfeited = 0;
 
         let src = &mut auction.to_account_info();
-        let dst = &mut winner.to_account_info();
-        transfer_from_owned_account(src, dst, bid_delta)?;
+        let dst = &mut penalty_recipient.to_account_info();
+
+        transfer_from_owned_account(src, dst, amount)?;
 
         Ok(())
     }
 
-    pub fn withdraw_winning_bid_sealed(ctx: Context<WithdrawWinningBidSealed>) -> ProgramResult {
+    // permissionless so penalties reach forfeited even if a non-revealer never comes back to reclaim
+    pub fn forfeit_unrevealed_sealed(ctx: Context<ForfeitUnrevealedSealed>) -> ProgramResult {
         let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
-        let owner: &Signer = &ctx.accounts.owner;
+        let bidder = &ctx.accounts.bidder;
 
         let clock: Clock = Clock::get().unwrap();
         let cur_time: u64 = clock.unix_timestamp as u64;
 
-        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
-        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
-        require!(cur_time > auction.reveal_period,Err(AuctionError::RevealPeriodNotOver.into()));
+        auction.require_phase(cur_time, &[Phase::Settling, Phase::Settled])?;
 
-        let index = auction.bidders.iter().position(|&x| x == auction.highest_bidder);
-        if let None = index {
-            return Err(AuctionError::NoWinningBid.into())
-        } else {
-            let amount;
-            if auction.first_price || auction.second_highest_bid == 0 {
-                amount = auction.highest_bid;
-            } else {
-                amount = auction.second_highest_bid;
-            }
+        let index = auction.bidders.iter().position(|&x| x == *bidder.key)
+            .ok_or(AuctionError::NotBidder)?;
 
-            require!(
-                !auction.winning_bid_withdrawn,
-                Err(AuctionError::AlreadyWithdrewBid.into())
-            );
+        require!(!auction.revealed[index], Err(AuctionError::BidNotForfeitable.into()));
 
-            auction.winning_bid_withdrawn = true;
+        let fake_bid = auction.fake_bids[index];
+        let penalty = auction.non_reveal_penalty(fake_bid);
+        auction.forfeited += penalty;
 
-            let src = &mut auction.to_account_info();
-            let dst = &mut owner.to_account_info();
+        auction.bidders.remove(index);
+        auction.sealed_bids.remove(index);
+        auction.fake_bids.remove(index);
+        auction.revealed.remove(index);
+        auction.commit_times.remove(index);
+        auction.ciphertexts.remove(index);
 
-            transfer_from_owned_account(src, dst, amount)?;
-        }
+        let src = &mut auction.to_account_info();
+        let dst = &mut bidder.to_account_info();
+
+        transfer_from_owned_account(src, dst, fake_bid - penalty)?;
 
         Ok(())
     }
 
-    pub fn reclaim_item_sealed(ctx: Context<ReclaimItemSealed>) -> ProgramResult {
+    // equal top bids only get random keys from a slot committed once reveals are over, see commit_raffle_draw
+    pub fn commit_tie_break_draw_sealed(ctx: Context<CommitTieBreakDrawSealed>) -> ProgramResult {
         let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
-        let auction_ata = &ctx.accounts.auction_ata;
-        let owner = &ctx.accounts.owner;
-        let owner_ata = &ctx.accounts.owner_ata;
-        let mint = &ctx.accounts.mint;
-        let token_program = &ctx.accounts.token_program;
-        let ata_program = &ctx.accounts.ata_program;
-        let system_program = &ctx.accounts.system_program;
-        let rent_sysvar = &ctx.accounts.rent_sysvar;
+        let slot_hashes = &ctx.accounts.slot_hashes;
 
         let clock: Clock = Clock::get().unwrap();
         let cur_time: u64 = clock.unix_timestamp as u64;
 
-        require!(
-            (auction.highest_bid == 0 && cur_time > auction.reveal_period) || auction.cancelled,
-            Err(AuctionError::RevealPeriodNotOver.into())
-        );
+        auction.require_phase(cur_time, &[Phase::Settling])?;
+        require!(auction.tie_pending(), Err(AuctionError::NoTieToDraw.into()));
 
-        let amount = auction.token_amount;
+        if auction.tie_draw_slot != 0 {
+            require!(
+                clock.slot > auction.tie_draw_slot
+                    && slot_hash_at(slot_hashes, auction.tie_draw_slot)?.is_none(),
+                Err(AuctionError::DrawSlotPending.into())
+            );
+        }
 
-        if owner_ata.to_account_info().data_is_empty() {
+        auction.tie_draw_slot = clock.slot + DRAW_DELAY;
+
+        Ok(())
+    }
+
+    pub fn draw_tie_break_sealed(ctx: Context<DrawTieBreakSealed>) -> ProgramResult {
+        let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
+        let slot_hashes = &ctx.accounts.slot_hashes;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        auction.require_phase(cur_time, &[Phase::Settling])?;
+        require!(auction.tie_pending(), Err(AuctionError::NoTieToDraw.into()));
+        require!(auction.tie_draw_slot != 0, Err(AuctionError::DrawNotCommitted.into()));
+        require!(clock.slot > auction.tie_draw_slot, Err(AuctionError::DrawSlotPending.into()));
+
+        let slot_hash = slot_hash_at(slot_hashes, auction.tie_draw_slot)?
+            .ok_or(AuctionError::DrawSlotExpired)?;
+
+        // every tied entry pays the same, so reordering them only changes who wins
+        let tied = auction.top_tie_len();
+        for entry in auction.reveal_book[..tied].iter_mut() {
+            entry.tie_key = random_u64(&slot_hash, &entry.bidder);
+        }
+        auction.reveal_book[..tied].sort_by_key(|entry| entry.tie_key);
+        auction.highest_bidder = auction.reveal_book[0].bidder;
+        auction.ties_drawn = true;
+
+        Ok(())
+    }
+
+    pub fn reveal_sealed_bid(ctx: Context<RevealSealedBid>, bid: u64, nonce: u64) -> ProgramResult {
+        let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+
+        require!(
+            auction.commitment_version == COMMITMENT_V1,
+            Err(AuctionError::WrongCommitmentVersion.into())
+        );
+
+        reveal_sealed(auction, &bidder.to_account_info(), bid, compute_bid_hash(bid, nonce))
+    }
+
+    pub fn reveal_sealed_bid_v2(ctx: Context<RevealSealedBid>, bid: u64, salt: [u8; 32]) -> ProgramResult {
+        let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+
+        require!(
+            auction.commitment_version == COMMITMENT_V2,
+            Err(AuctionError::WrongCommitmentVersion.into())
+        );
+
+        let proposed_hash = compute_bid_hash_v2(&auction.key(), bidder.key, bid, &salt);
+        reveal_sealed(auction, &bidder.to_account_info(), bid, proposed_hash)
+    }
+
+    // a keeper decrypts the bidder's ciphertext off-chain and reveals on their behalf
+    // no signer: the auctioneer key is an x25519 key that can't sign, the v2 hash alone authorizes the reveal
+    pub fn reveal_sealed_bid_for(ctx: Context<RevealSealedBidFor>, bid: u64, salt: [u8; 32]) -> ProgramResult {
+        let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
+        let bidder = &ctx.accounts.bidder;
+
+        require!(auction.auctioneer != Pubkey::default(), Err(AuctionError::NoAuctioneer.into()));
+        require!(
+            auction.commitment_version == COMMITMENT_V2,
+            Err(AuctionError::WrongCommitmentVersion.into())
+        );
+
+        let proposed_hash = compute_bid_hash_v2(&auction.key(), bidder.key, bid, &salt);
+        reveal_sealed(auction, &bidder.to_account_info(), bid, proposed_hash)
+    }
+
+    // a relayer submits a reveal the bidder signed off-chain, the signature is checked by an
+    // ed25519 instruction placed right before this one
+    pub fn reveal_sealed_bid_signed(ctx: Context<RevealSealedBidSigned>, bid: u64, salt: [u8; 32]) -> ProgramResult {
+        let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
+        let bidder = &ctx.accounts.bidder;
+        let instructions_sysvar = &ctx.accounts.instructions_sysvar;
+
+        require!(
+            auction.commitment_version == COMMITMENT_V2,
+            Err(AuctionError::WrongCommitmentVersion.into())
+        );
+
+        let cur_index = load_current_index_checked(instructions_sysvar)? as usize;
+        require!(cur_index > 0, Err(AuctionError::InvalidRevealSignature.into()));
+
+        let signature_ix = load_instruction_at_checked(cur_index - 1, instructions_sysvar)?;
+        let message = reveal_message(&auction.key(), bid, &salt);
+        require!(
+            verify_ed25519_instruction(&signature_ix, bidder.key, &message),
+            Err(AuctionError::InvalidRevealSignature.into())
+        );
+
+        let proposed_hash = compute_bid_hash_v2(&auction.key(), bidder.key, bid, &salt);
+        reveal_sealed(auction, &bidder.to_account_info(), bid, proposed_hash)
+    }
+
+    pub fn withdraw_item_sealed<'info>(ctx: Context<'_, '_, '_, 'info, WithdrawItemSealed<'info>>) -> ProgramResult {
+        let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let winner = &ctx.accounts.highest_bidder;
+        let winner_ata = &ctx.accounts.highest_bidder_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        auction.require_phase(cur_time, &[Phase::Settling])?;
+        require!(!auction.item_withdrawn, Err(AuctionError::ItemAlreadyWithdrawn.into()));
+        require!(auction.highest_bid > 0, Err(AuctionError::NoWinningBid.into()));
+        require!(!auction.tie_pending(), Err(AuctionError::TieNotDrawn.into()));
+
+        let amount = auction.token_amount;
+
+        if winner_ata.to_account_info().data_is_empty() {
+            create_ata(
+                winner.to_account_info(),
+                winner.to_account_info(),
+                mint.to_account_info(),
+                winner_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            winner_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"sealed auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        release_bundle(
+            winner.to_account_info(),
+            auction.to_account_info(),
+            &auction.bundle,
+            ctx.remaining_accounts,
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info(),
+            &[&[b"sealed auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        // refund difference between sent SOL and real bid SOL
+        // must happen last bc solana does a pre-cpi account balance check
+        // so we can't transfer sol before create_ata or solana will think smth is wrong
+        let index = auction.bidders.iter().position(|&x| x == *winner.key);
+        let fake_bid = auction.fake_bids[index.unwrap()];
+        let bid_delta = fake_bid - auction.winning_payment();
+
+        auction.item_withdrawn = true;
+        auction.transition(cur_time);
+
+        let src = &mut auction.to_account_info();
+        let dst = &mut winner.to_account_info();
+        transfer_from_owned_account(src, dst, bid_delta)?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_winning_bid_sealed(ctx: Context<WithdrawWinningBidSealed>) -> ProgramResult {
+        let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
+        let owner: &Signer = &ctx.accounts.owner;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        auction.require_phase(cur_time, &[Phase::Settling])?;
+        require!(!auction.tie_pending(), Err(AuctionError::TieNotDrawn.into()));
+
+        let index = auction.bidders.iter().position(|&x| x == auction.highest_bidder);
+        if let None = index {
+            return Err(AuctionError::NoWinningBid.into())
+        } else {
+            let amount = auction.winning_payment();
+
+            require!(
+                !auction.winning_bid_withdrawn,
+                Err(AuctionError::AlreadyWithdrewBid.into())
+            );
+
+            auction.winning_bid_withdrawn = true;
+            auction.transition(cur_time);
+
+            let src = &mut auction.to_account_info();
+            let dst = &mut owner.to_account_info();
+
+            transfer_from_owned_account(src, dst, amount)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn reclaim_item_sealed<'info>(ctx: Context<'_, '_, '_, 'info, ReclaimItemSealed<'info>>) -> ProgramResult {
+        let auction: &mut Account<SealedAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let owner_ata = &ctx.accounts.owner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        auction.require_phase(cur_time, &[Phase::Settling, Phase::Cancelled])?;
+        require!(
+            auction.highest_bid == 0 || auction.cancelled,
+            Err(AuctionError::ItemAlreadySold.into())
+        );
+        require!(!auction.item_withdrawn, Err(AuctionError::ItemAlreadyWithdrawn.into()));
+
+        let amount = auction.token_amount;
+
+        auction.item_withdrawn = true;
+        auction.transition(cur_time);
+
+        if owner_ata.to_account_info().data_is_empty() {
             create_ata(
                 owner.to_account_info(),
                 owner.to_account_info(),
@@ -637,6 +1558,2622 @@ pub mod auctionhouse {
             &[&[b"sealed auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
         )?;
 
+        release_bundle(
+            owner.to_account_info(),
+            auction.to_account_info(),
+            &auction.bundle,
+            ctx.remaining_accounts,
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info(),
+            &[&[b"sealed auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn create_dutch_auction(
+        ctx: Context<CreateDutchAuction>,
+        bump: u8,
+        title: String,
+        start_price: u64,
+        floor: u64,
+        decay_curve: DecayCurve,
+        half_life: u64,
+        start_time: u64,
+        end_time: u64,
+        token_amount: u64,
+    ) -> ProgramResult {
+        let auction: &mut Account<DutchAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let owner_ata = &ctx.accounts.owner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(title.chars().count() <= 50, Err(AuctionError::TitleOverflow.into()));
+        require!(token_amount != 0, Err(AuctionError::InvalidTokenAmount.into()));
+        require!(start_time < end_time, Err(AuctionError::InvalidStartTime.into()));
+        require!(cur_time > start_time || start_time == 0, Err(AuctionError::InvalidStartTime.into()));
+        require!(cur_time < end_time, Err(AuctionError::InvalidEndTime.into()));
+        require!(floor > 0, Err(AuctionError::InvalidBidFloor.into()));
+        require!(start_price > floor, Err(AuctionError::InvalidStartPrice.into()));
+        require!(
+            decay_curve != DecayCurve::Exponential || half_life > 0,
+            Err(AuctionError::InvalidHalfLife.into())
+        );
+
+        auction.owner = *owner.key;
+        auction.mint = mint.key();
+        auction.token_amount = token_amount;
+
+        auction.start_time = if start_time == 0 { cur_time } else { start_time };
+        auction.end_time = end_time;
+        auction.cancelled = false;
+
+        auction.title = title;
+
+        auction.start_price = start_price;
+        auction.bid_floor = floor;
+        auction.decay_curve = decay_curve;
+        auction.half_life = half_life;
+
+        auction.sale_price = 0;
+        auction.sold = false;
+
+        auction.bump = bump;
+
+        create_ata(
+            owner.to_account_info(),
+            auction.to_account_info(),
+            mint.to_account_info(),
+            auction_ata.to_account_info(),
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info()
+        )?;
+
+        transfer_spl(
+            owner.to_account_info(),
+            owner_ata.to_account_info(),
+            auction_ata.to_account_info(),
+            token_amount,
+            token_program.to_account_info(),
+            &[]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn cancel_dutch_auction(ctx: Context<CancelDutchAuction>) -> ProgramResult {
+        let auction: &mut Account<DutchAuction> = &mut ctx.accounts.auction;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.sold, Err(AuctionError::ItemAlreadySold.into()));
+        require!(cur_time < auction.end_time, Err(AuctionError::CannotCancelAfterClose.into()));
+
+        auction.cancelled = true;
+
         Ok(())
     }
+
+    pub fn buy_dutch(ctx: Context<BuyDutch>) -> ProgramResult {
+        let auction: &mut Account<DutchAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let buyer = &ctx.accounts.buyer;
+        let buyer_ata = &ctx.accounts.buyer_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(!auction.sold, Err(AuctionError::ItemAlreadySold.into()));
+        require!(cur_time > auction.start_time, Err(AuctionError::BidBeforeStart.into()));
+        require!(cur_time < auction.end_time, Err(AuctionError::BidAfterClose.into()));
+        require!(*buyer.key != auction.owner, Err(AuctionError::OwnerCannotBid.into()));
+
+        let price = compute_dutch_price(
+            auction.start_price,
+            auction.bid_floor,
+            auction.decay_curve,
+            auction.half_life,
+            auction.start_time,
+            auction.end_time,
+            cur_time
+        );
+
+        auction.buyer = *buyer.key;
+        auction.sale_price = price;
+        auction.sold = true;
+
+        let amount = auction.token_amount;
+
+        transfer_sol(
+            buyer.to_account_info(),
+            owner.to_account_info(),
+            price,
+            system_program.to_account_info()
+        )?;
+
+        if buyer_ata.to_account_info().data_is_empty() {
+            create_ata(
+                buyer.to_account_info(),
+                buyer.to_account_info(),
+                mint.to_account_info(),
+                buyer_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            buyer_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"dutch auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn reclaim_item_dutch(ctx: Context<ReclaimItemDutch>) -> ProgramResult {
+        let auction: &mut Account<DutchAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let owner_ata = &ctx.accounts.owner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.sold, Err(AuctionError::ItemAlreadySold.into()));
+        require!(
+            cur_time > auction.end_time || auction.cancelled,
+            Err(AuctionError::AuctionNotOver.into())
+        );
+
+        let amount = auction.token_amount;
+
+        if owner_ata.to_account_info().data_is_empty() {
+            create_ata(
+                owner.to_account_info(),
+                owner.to_account_info(),
+                mint.to_account_info(),
+                owner_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            owner_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"dutch auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn create_multi_unit_sealed_auction(
+        ctx: Context<CreateMultiUnitSealedAuction>,
+        bump: u8,
+        title: String,
+        floor: u64,
+        clearing_rule: ClearingRule,
+        start_time: u64,
+        end_time: u64,
+        reveal_period: u64,
+        bidder_cap: u64,
+        token_amount: u64,
+        units: u64,
+    ) -> ProgramResult {
+        let auction: &mut Account<MultiUnitSealedAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let owner_ata = &ctx.accounts.owner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(title.chars().count() <= 50, Err(AuctionError::TitleOverflow.into()));
+        require!(token_amount != 0, Err(AuctionError::InvalidTokenAmount.into()));
+        require!(
+            units > 0 && token_amount % units == 0,
+            Err(AuctionError::InvalidUnits.into())
+        );
+        require!(start_time < end_time, Err(AuctionError::InvalidStartTime.into()));
+        require!(cur_time > start_time || start_time == 0, Err(AuctionError::InvalidStartTime.into()));
+        require!(cur_time < end_time, Err(AuctionError::InvalidEndTime.into()));
+        require!(reveal_period > end_time, Err(AuctionError::InvalidRevealPeriod.into()));
+        require!(floor > 0, Err(AuctionError::InvalidBidFloor.into()));
+        require!(
+            clearing_rule != ClearingRule::Vcg || bidder_cap <= MAX_VCG_BIDDERS,
+            Err(AuctionError::InvalidVcgBidderCap.into())
+        );
+
+        auction.clearing_rule = clearing_rule;
+
+        auction.owner = *owner.key;
+        auction.mint = mint.key();
+        auction.token_amount = token_amount;
+        auction.units = units;
+
+        auction.start_time = if start_time == 0 { cur_time } else { start_time };
+        auction.end_time = end_time;
+        auction.reveal_period = reveal_period;
+        auction.cancelled = false;
+
+        auction.title = title;
+
+        auction.bidder_cap = bidder_cap;
+        auction.bid_floor = floor;
+
+        auction.settled = false;
+        auction.clearing_price = 0;
+        auction.units_sold = 0;
+        auction.proceeds_withdrawn = false;
+        auction.item_reclaimed = false;
+
+        auction.bump = bump;
+
+        create_ata(
+            owner.to_account_info(),
+            auction.to_account_info(),
+            mint.to_account_info(),
+            auction_ata.to_account_info(),
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info()
+        )?;
+
+        transfer_spl(
+            owner.to_account_info(),
+            owner_ata.to_account_info(),
+            auction_ata.to_account_info(),
+            token_amount,
+            token_program.to_account_info(),
+            &[]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn cancel_multi_unit_sealed_auction(ctx: Context<CancelMultiUnitSealedAuction>) -> ProgramResult {
+        let auction: &mut Account<MultiUnitSealedAuction> = &mut ctx.accounts.auction;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(cur_time < auction.end_time, Err(AuctionError::CannotCancelRevealPeriod.into()));
+
+        auction.cancelled = true;
+
+        Ok(())
+    }
+
+    pub fn make_multi_unit_sealed_bid(
+        ctx: Context<MakeMultiUnitSealedBid>,
+        bid_hash: [u8; 32],
+        amount: u64
+    ) -> ProgramResult {
+        let auction: &mut Account<MultiUnitSealedAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+        let system_program = &ctx.accounts.system_program;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.start_time, Err(AuctionError::BidBeforeStart.into()));
+        require!(cur_time < auction.end_time, Err(AuctionError::BidAfterClose.into()));
+        require!(*bidder.key != auction.owner, Err(AuctionError::OwnerCannotBid.into()));
+        require!(amount > 0, Err(AuctionError::MustSendSol.into()));
+
+        let index = auction.bidders.iter().position(|&x| x == *bidder.key);
+
+        require!(index.is_none(), Err(AuctionError::DuplicateSealedBid.into()));
+        require!(
+            auction.bidders.len() < (auction.bidder_cap as usize),
+            Err(AuctionError::BidderCapReached.into())
+        );
+
+        auction.bidders.push(*bidder.key);
+        auction.sealed_bids.push(bid_hash);
+        auction.fake_bids.push(amount);
+        auction.bid_units.push(0);
+        auction.bid_prices.push(0);
+        auction.allocations.push(0);
+
+        transfer_sol(
+            bidder.to_account_info(),
+            auction.to_account_info(),
+            amount,
+            system_program.to_account_info()
+        )?;
+
+        Ok(())
+    }
+
+    pub fn reclaim_multi_unit_sealed_bid(ctx: Context<ReclaimMultiUnitSealedBid>) -> ProgramResult {
+        let auction: &mut Account<MultiUnitSealedAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        // after bidding closes, refunds go through settlement so allocations stay consistent
+        require!(
+            cur_time < auction.end_time || auction.cancelled,
+            Err(AuctionError::BidAfterClose.into())
+        );
+
+        let index = auction.bidders.iter().position(|&x| x == *bidder.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        let fake_bid = auction.fake_bids[index];
+
+        auction.bidders.remove(index);
+        auction.sealed_bids.remove(index);
+        auction.fake_bids.remove(index);
+        auction.bid_units.remove(index);
+        auction.bid_prices.remove(index);
+        auction.allocations.remove(index);
+
+        let src = &mut auction.to_account_info();
+        let dst = &mut bidder.to_account_info();
+
+        transfer_from_owned_account(src, dst, fake_bid)?;
+
+        Ok(())
+    }
+
+    pub fn reveal_multi_unit_sealed_bid(
+        ctx: Context<RevealMultiUnitSealedBid>,
+        units: u64,
+        price: u64,
+        salt: [u8; 32]
+    ) -> ProgramResult {
+        let auction: &mut Account<MultiUnitSealedAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
+        require!(cur_time < auction.reveal_period, Err(AuctionError::RevealPeriodOver.into()));
+
+        let index = auction.bidders.iter().position(|&x| x == *bidder.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        let fake_bid = auction.fake_bids[index];
+
+        let bid_hash = auction.sealed_bids[index];
+        let proposed_hash = compute_multi_unit_bid_hash(&auction.key(), bidder.key, units, price, &salt);
+
+        require!(bid_hash == proposed_hash, Err(AuctionError::HashMismatch.into()));
+        require!(
+            units > 0 && units <= auction.units,
+            Err(AuctionError::InvalidBidUnits.into())
+        );
+        require!(price > auction.bid_floor, Err(AuctionError::UnderBidFloor.into()));
+        require!(
+            (units as u128) * (price as u128) <= fake_bid as u128,
+            Err(AuctionError::InsufficientSol.into())
+        );
+
+        auction.bid_units[index] = units;
+        auction.bid_prices[index] = price;
+
+        Ok(())
+    }
+
+    pub fn settle_multi_unit_sealed_auction(ctx: Context<SettleMultiUnitSealedAuction>) -> ProgramResult {
+        let auction: &mut Account<MultiUnitSealedAuction> = &mut ctx.accounts.auction;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.reveal_period, Err(AuctionError::RevealPeriodNotOver.into()));
+        require!(!auction.settled, Err(AuctionError::AlreadySettled.into()));
+
+        let allocations = allocate_units(auction.units, &auction.bid_units, &auction.bid_prices);
+        let units_sold: u64 = allocations.iter().sum();
+
+        auction.clearing_price = if units_sold == 0 {
+            0
+        } else {
+            compute_clearing_price(
+                auction.clearing_rule,
+                auction.bid_floor,
+                &auction.bid_units,
+                &auction.bid_prices,
+                &allocations
+            )
+        };
+        auction.payments = if auction.clearing_rule == ClearingRule::Vcg {
+            compute_vcg_payments(
+                auction.units,
+                auction.bid_floor,
+                &auction.bid_units,
+                &auction.bid_prices,
+                &allocations
+            )
+        } else {
+            allocations.iter().map(|&a| a * auction.clearing_price).collect()
+        };
+        auction.allocations = allocations;
+        auction.units_sold = units_sold;
+        auction.settled = true;
+
+        Ok(())
+    }
+
+    pub fn withdraw_multi_unit_sealed(ctx: Context<WithdrawMultiUnitSealed>) -> ProgramResult {
+        let auction: &mut Account<MultiUnitSealedAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let bidder = &ctx.accounts.bidder;
+        let bidder_ata = &ctx.accounts.bidder_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        require!(auction.settled, Err(AuctionError::NotSettled.into()));
+
+        let i = auction.bidders.iter().position(|&x| x == *bidder.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        let fake_bid = auction.fake_bids[i];
+        require!(fake_bid > 0, Err(AuctionError::NothingToWithdraw.into()));
+
+        let allocation = auction.allocations[i];
+        let refund = fake_bid - auction.payments[i];
+
+        auction.fake_bids[i] = 0;
+
+        if allocation > 0 {
+            let amount = allocation * (auction.token_amount / auction.units);
+
+            if bidder_ata.to_account_info().data_is_empty() {
+                create_ata(
+                    bidder.to_account_info(),
+                    bidder.to_account_info(),
+                    mint.to_account_info(),
+                    bidder_ata.to_account_info(),
+                    token_program.to_account_info(),
+                    ata_program.to_account_info(),
+                    system_program.to_account_info(),
+                    rent_sysvar.to_account_info()
+                )?;
+            }
+
+            transfer_spl(
+                auction.to_account_info(),
+                auction_ata.to_account_info(),
+                bidder_ata.to_account_info(),
+                amount,
+                token_program.to_account_info(),
+                &[&[b"multi unit sealed auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+            )?;
+        }
+
+        // refund difference between sent SOL and the payment for the units won
+        // must happen last, see withdraw_item_sealed
+        let src = &mut auction.to_account_info();
+        let dst = &mut bidder.to_account_info();
+        transfer_from_owned_account(src, dst, refund)?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_proceeds_multi_unit_sealed(ctx: Context<WithdrawProceedsMultiUnitSealed>) -> ProgramResult {
+        let auction: &mut Account<MultiUnitSealedAuction> = &mut ctx.accounts.auction;
+        let owner: &Signer = &ctx.accounts.owner;
+
+        require!(auction.settled, Err(AuctionError::NotSettled.into()));
+        require!(auction.units_sold > 0, Err(AuctionError::NoWinningBid.into()));
+        require!(
+            !auction.proceeds_withdrawn,
+            Err(AuctionError::AlreadyWithdrewBid.into())
+        );
+
+        auction.proceeds_withdrawn = true;
+
+        let amount: u64 = auction.payments.iter().sum();
+
+        let src = &mut auction.to_account_info();
+        let dst = &mut owner.to_account_info();
+
+        transfer_from_owned_account(src, dst, amount)?;
+
+        Ok(())
+    }
+
+    pub fn reclaim_item_multi_unit_sealed(ctx: Context<ReclaimItemMultiUnitSealed>) -> ProgramResult {
+        let auction: &mut Account<MultiUnitSealedAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let owner_ata = &ctx.accounts.owner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        require!(
+            auction.settled || auction.cancelled,
+            Err(AuctionError::NotSettled.into())
+        );
+        require!(!auction.item_reclaimed, Err(AuctionError::ItemAlreadyReclaimed.into()));
+
+        // unsold units go back to the seller
+        let amount = (auction.units - auction.units_sold) * (auction.token_amount / auction.units);
+        require!(amount > 0, Err(AuctionError::NothingToWithdraw.into()));
+
+        auction.item_reclaimed = true;
+
+        if owner_ata.to_account_info().data_is_empty() {
+            create_ata(
+                owner.to_account_info(),
+                owner.to_account_info(),
+                mint.to_account_info(),
+                owner_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            owner_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"multi unit sealed auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn create_reverse_auction(
+        ctx: Context<CreateReverseAuction>,
+        bump: u8,
+        title: String,
+        budget: u64,
+        decrement: u64,
+        start_time: u64,
+        end_time: u64,
+        supplier_cap: u64,
+        token_amount: u64,
+    ) -> ProgramResult {
+        let auction: &mut Account<ReverseAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(title.chars().count() <= 50, Err(AuctionError::TitleOverflow.into()));
+        require!(decrement != 0, Err(AuctionError::InvalidDecrement.into()));
+        require!(token_amount != 0, Err(AuctionError::InvalidTokenAmount.into()));
+        require!(start_time < end_time, Err(AuctionError::InvalidStartTime.into()));
+        require!(cur_time > start_time || start_time == 0, Err(AuctionError::InvalidStartTime.into()));
+        require!(cur_time < end_time, Err(AuctionError::InvalidEndTime.into()));
+        require!(budget > 0, Err(AuctionError::InvalidBudget.into()));
+
+        auction.owner = *owner.key;
+        auction.mint = mint.key();
+        auction.token_amount = token_amount;
+        auction.budget = budget;
+
+        auction.start_time = if start_time == 0 { cur_time } else { start_time };
+        auction.end_time = end_time;
+        auction.cancelled = false;
+
+        auction.title = title;
+
+        auction.supplier_cap = supplier_cap;
+        auction.lowest_offer = 0;
+        auction.min_offer_decrement = decrement;
+
+        auction.item_withdrawn = false;
+        auction.payment_withdrawn = false;
+        auction.budget_reclaimed = false;
+
+        auction.bump = bump;
+
+        // the auction ata holds every supplier's escrowed tokens
+        create_ata(
+            owner.to_account_info(),
+            auction.to_account_info(),
+            mint.to_account_info(),
+            auction_ata.to_account_info(),
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info()
+        )?;
+
+        transfer_sol(
+            owner.to_account_info(),
+            auction.to_account_info(),
+            budget,
+            system_program.to_account_info()
+        )?;
+
+        Ok(())
+    }
+
+    pub fn cancel_reverse_auction(ctx: Context<CancelReverseAuction>) -> ProgramResult {
+        let auction: &mut Account<ReverseAuction> = &mut ctx.accounts.auction;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(cur_time < auction.end_time, Err(AuctionError::CannotCancelAfterClose.into()));
+
+        auction.cancelled = true;
+
+        Ok(())
+    }
+
+    pub fn make_reverse_offer(ctx: Context<MakeReverseOffer>, amount: u64) -> ProgramResult {
+        let auction: &mut Account<ReverseAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let supplier = &ctx.accounts.supplier;
+        let supplier_ata = &ctx.accounts.supplier_ata;
+        let token_program = &ctx.accounts.token_program;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.start_time, Err(AuctionError::BidBeforeStart.into()));
+        require!(cur_time < auction.end_time, Err(AuctionError::BidAfterClose.into()));
+        require!(*supplier.key != auction.owner, Err(AuctionError::OwnerCannotBid.into()));
+        // offers are escrowed in the auction's ata, which the seller withdraws from later
+        require!(
+            *auction_ata.key == spl_associated_token_account::get_associated_token_address(&auction.key(), &auction.mint),
+            Err(AuctionError::InvalidAuctionAta.into())
+        );
+        require!(
+            amount > 0 && amount <= auction.budget,
+            Err(AuctionError::OfferOverBudget.into())
+        );
+        require!(
+            auction.lowest_offer == 0 || amount + auction.min_offer_decrement < auction.lowest_offer,
+            Err(AuctionError::InsufficientOffer.into())
+        );
+
+        let index = auction.suppliers.iter().position(|&x| x == *supplier.key);
+
+        // suppliers escrow their tokens with their first offer so delivery can't fail
+        let new_supplier = index.is_none();
+        if let Some(i) = index {
+            auction.offers[i] = amount;
+        } else {
+            require!(
+                auction.suppliers.len() < (auction.supplier_cap as usize),
+                Err(AuctionError::BidderCapReached.into())
+            );
+            auction.suppliers.push(*supplier.key);
+            auction.offers.push(amount);
+        }
+
+        auction.lowest_supplier = *supplier.key;
+        auction.lowest_offer = amount;
+
+        if new_supplier {
+            transfer_spl(
+                supplier.to_account_info(),
+                supplier_ata.to_account_info(),
+                auction_ata.to_account_info(),
+                auction.token_amount,
+                token_program.to_account_info(),
+                &[]
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn reclaim_reverse_offer(ctx: Context<ReclaimReverseOffer>) -> ProgramResult {
+        let auction: &mut Account<ReverseAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let supplier = &ctx.accounts.supplier;
+        let supplier_ata = &ctx.accounts.supplier_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let index = auction.suppliers.iter().position(|&x| x == *supplier.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        require!(
+            *auction_ata.key == spl_associated_token_account::get_associated_token_address(&auction.key(), &auction.mint),
+            Err(AuctionError::InvalidAuctionAta.into())
+        );
+        require!(
+            *supplier.key != auction.lowest_supplier || auction.cancelled,
+            Err(AuctionError::WinnerCannotWithdrawBid.into())
+        );
+
+        auction.suppliers.remove(index);
+        auction.offers.remove(index);
+
+        let amount = auction.token_amount;
+
+        if supplier_ata.to_account_info().data_is_empty() {
+            create_ata(
+                supplier.to_account_info(),
+                supplier.to_account_info(),
+                mint.to_account_info(),
+                supplier_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            supplier_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"reverse auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_item_reverse(ctx: Context<WithdrawItemReverse>) -> ProgramResult {
+        let auction: &mut Account<ReverseAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let owner_ata = &ctx.accounts.owner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
+        require!(auction.lowest_offer > 0, Err(AuctionError::NoWinningOffer.into()));
+        require!(!auction.item_withdrawn, Err(AuctionError::ItemAlreadyWithdrawn.into()));
+
+        auction.item_withdrawn = true;
+
+        let amount = auction.token_amount;
+
+        if owner_ata.to_account_info().data_is_empty() {
+            create_ata(
+                owner.to_account_info(),
+                owner.to_account_info(),
+                mint.to_account_info(),
+                owner_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            owner_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"reverse auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_payment_reverse(ctx: Context<WithdrawPaymentReverse>) -> ProgramResult {
+        let auction: &mut Account<ReverseAuction> = &mut ctx.accounts.auction;
+        let supplier: &Signer = &ctx.accounts.lowest_supplier;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
+        require!(auction.lowest_offer > 0, Err(AuctionError::NoWinningOffer.into()));
+        require!(!auction.payment_withdrawn, Err(AuctionError::AlreadyWithdrewPayment.into()));
+
+        auction.payment_withdrawn = true;
+
+        let amount = auction.lowest_offer;
+
+        let src = &mut auction.to_account_info();
+        let dst = &mut supplier.to_account_info();
+
+        transfer_from_owned_account(src, dst, amount)?;
+
+        Ok(())
+    }
+
+    pub fn reclaim_budget_reverse(ctx: Context<ReclaimBudgetReverse>) -> ProgramResult {
+        let auction: &mut Account<ReverseAuction> = &mut ctx.accounts.auction;
+        let owner: &Signer = &ctx.accounts.owner;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(
+            cur_time > auction.end_time || auction.cancelled,
+            Err(AuctionError::AuctionNotOver.into())
+        );
+        require!(!auction.budget_reclaimed, Err(AuctionError::BudgetAlreadyReclaimed.into()));
+
+        auction.budget_reclaimed = true;
+
+        // the winning offer stays escrowed for the supplier
+        let amount = if auction.cancelled {
+            auction.budget
+        } else {
+            auction.budget - auction.lowest_offer
+        };
+
+        let src = &mut auction.to_account_info();
+        let dst = &mut owner.to_account_info();
+
+        transfer_from_owned_account(src, dst, amount)?;
+
+        Ok(())
+    }
+
+    pub fn create_clock_auction(
+        ctx: Context<CreateClockAuction>,
+        bump: u8,
+        title: String,
+        floor: u64,
+        increment: u64,
+        step_length: u64,
+        start_time: u64,
+        bidder_cap: u64,
+        token_amount: u64,
+    ) -> ProgramResult {
+        let auction: &mut Account<ClockAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let owner_ata = &ctx.accounts.owner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(title.chars().count() <= 50, Err(AuctionError::TitleOverflow.into()));
+        require!(increment != 0, Err(AuctionError::InvalidIncrement.into()));
+        require!(step_length != 0, Err(AuctionError::InvalidStepLength.into()));
+        require!(token_amount != 0, Err(AuctionError::InvalidTokenAmount.into()));
+        // bidders register before the clock starts
+        require!(cur_time < start_time, Err(AuctionError::InvalidStartTime.into()));
+        require!(floor > 0, Err(AuctionError::InvalidBidFloor.into()));
+
+        auction.owner = *owner.key;
+        auction.mint = mint.key();
+        auction.token_amount = token_amount;
+
+        auction.start_time = start_time;
+        auction.cancelled = false;
+
+        auction.title = title;
+
+        auction.bid_floor = floor;
+        auction.price_increment = increment;
+        auction.step_length = step_length;
+
+        auction.bidder_cap = bidder_cap;
+
+        auction.settled = false;
+        auction.clearing_price = 0;
+        auction.winning_bid_withdrawn = false;
+        auction.item_withdrawn = false;
+
+        auction.bump = bump;
+
+        create_ata(
+            owner.to_account_info(),
+            auction.to_account_info(),
+            mint.to_account_info(),
+            auction_ata.to_account_info(),
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info()
+        )?;
+
+        transfer_spl(
+            owner.to_account_info(),
+            owner_ata.to_account_info(),
+            auction_ata.to_account_info(),
+            token_amount,
+            token_program.to_account_info(),
+            &[]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn cancel_clock_auction(ctx: Context<CancelClockAuction>) -> ProgramResult {
+        let auction: &mut Account<ClockAuction> = &mut ctx.accounts.auction;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(cur_time < auction.start_time, Err(AuctionError::ClockStarted.into()));
+
+        auction.cancelled = true;
+
+        Ok(())
+    }
+
+    pub fn register_clock_bidder(ctx: Context<RegisterClockBidder>, amount: u64) -> ProgramResult {
+        let auction: &mut Account<ClockAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+        let system_program = &ctx.accounts.system_program;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time < auction.start_time, Err(AuctionError::ClockStarted.into()));
+        require!(*bidder.key != auction.owner, Err(AuctionError::OwnerCannotBid.into()));
+        require!(amount >= auction.bid_floor, Err(AuctionError::UnderBidFloor.into()));
+
+        let index = auction.bidders.iter().position(|&x| x == *bidder.key);
+
+        require!(index.is_none(), Err(AuctionError::AlreadyRegistered.into()));
+        require!(
+            auction.bidders.len() < (auction.bidder_cap as usize),
+            Err(AuctionError::BidderCapReached.into())
+        );
+
+        auction.bidders.push(*bidder.key);
+        auction.deposits.push(amount);
+        auction.exit_prices.push(0);
+
+        transfer_sol(
+            bidder.to_account_info(),
+            auction.to_account_info(),
+            amount,
+            system_program.to_account_info()
+        )?;
+
+        Ok(())
+    }
+
+    pub fn top_up_clock_deposit(ctx: Context<TopUpClockDeposit>, amount: u64) -> ProgramResult {
+        let auction: &mut Account<ClockAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+        let system_program = &ctx.accounts.system_program;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(!auction.settled, Err(AuctionError::AlreadySettled.into()));
+        require!(amount > 0, Err(AuctionError::MustSendSol.into()));
+
+        let index = auction.bidders.iter().position(|&x| x == *bidder.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        // a deposit the clock has already passed can't be revived
+        require!(
+            auction.exit_price(index, cur_time).is_none(),
+            Err(AuctionError::BidderDroppedOut.into())
+        );
+
+        auction.deposits[index] += amount;
+
+        transfer_sol(
+            bidder.to_account_info(),
+            auction.to_account_info(),
+            amount,
+            system_program.to_account_info()
+        )?;
+
+        Ok(())
+    }
+
+    pub fn drop_out_clock(ctx: Context<DropOutClock>) -> ProgramResult {
+        let auction: &mut Account<ClockAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(!auction.settled, Err(AuctionError::AlreadySettled.into()));
+        require!(cur_time >= auction.start_time, Err(AuctionError::ClockNotStarted.into()));
+
+        let index = auction.bidders.iter().position(|&x| x == *bidder.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        require!(
+            auction.exit_price(index, cur_time).is_none(),
+            Err(AuctionError::BidderDroppedOut.into())
+        );
+        require!(
+            auction.active_bidders(cur_time) > 1,
+            Err(AuctionError::LastBidderRemaining.into())
+        );
+
+        auction.exit_prices[index] = auction.clock_price(cur_time);
+
+        Ok(())
+    }
+
+    pub fn settle_clock_auction(ctx: Context<SettleClockAuction>) -> ProgramResult {
+        let auction: &mut Account<ClockAuction> = &mut ctx.accounts.auction;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(!auction.settled, Err(AuctionError::AlreadySettled.into()));
+        require!(cur_time >= auction.start_time, Err(AuctionError::ClockNotStarted.into()));
+        require!(
+            auction.active_bidders(cur_time) <= 1,
+            Err(AuctionError::ClockStillRunning.into())
+        );
+
+        let exits: Vec<Option<u64>> = (0..auction.bidders.len())
+            .map(|i| auction.exit_price(i, cur_time))
+            .collect();
+
+        // a bidder whose deposit ran out was still in at that price, so they outrank an explicit drop-out at it
+        let ranks: Vec<Option<(u64, bool)>> = exits.iter().enumerate()
+            .map(|(i, exit)| exit.map(|price| (price, auction.exit_prices[i] == 0)))
+            .collect();
+
+        // the last bidder in wins, otherwise the highest exit with ties going to the earliest registrant
+        let mut winner: Option<usize> = exits.iter().position(|x| x.is_none());
+        if winner.is_none() {
+            for (i, rank) in ranks.iter().enumerate() {
+                if winner.map_or(true, |w| *rank > ranks[w]) {
+                    winner = Some(i);
+                }
+            }
+        }
+
+        if let Some(w) = winner {
+            // the winner pays the price at which the second-to-last bidder dropped
+            let clearing_price = exits.iter().enumerate()
+                .filter(|&(i, _)| i != w)
+                .filter_map(|(_, exit)| *exit)
+                .max()
+                .unwrap_or(auction.bid_floor);
+
+            auction.winner = auction.bidders[w];
+            auction.clearing_price = clearing_price;
+        }
+
+        auction.settled = true;
+
+        Ok(())
+    }
+
+    pub fn reclaim_clock_deposit(ctx: Context<ReclaimClockDeposit>) -> ProgramResult {
+        let auction: &mut Account<ClockAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+
+        // deposits stay escrowed until settlement since any of them could still win a tie
+        require!(
+            auction.settled || auction.cancelled,
+            Err(AuctionError::NotSettled.into())
+        );
+
+        let index = auction.bidders.iter().position(|&x| x == *bidder.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        require!(
+            *bidder.key != auction.winner || auction.clearing_price == 0 || auction.cancelled,
+            Err(AuctionError::WinnerCannotWithdrawBid.into())
+        );
+
+        let amount = auction.deposits[index];
+        require!(amount > 0, Err(AuctionError::NothingToWithdraw.into()));
+
+        auction.deposits[index] = 0;
+
+        let src = &mut auction.to_account_info();
+        let dst = &mut bidder.to_account_info();
+
+        transfer_from_owned_account(src, dst, amount)?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_item_clock(ctx: Context<WithdrawItemClock>) -> ProgramResult {
+        let auction: &mut Account<ClockAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let winner = &ctx.accounts.winner;
+        let winner_ata = &ctx.accounts.winner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        require!(auction.settled, Err(AuctionError::NotSettled.into()));
+        require!(auction.clearing_price > 0, Err(AuctionError::NoWinningBid.into()));
+        require!(!auction.item_withdrawn, Err(AuctionError::ItemAlreadyWithdrawn.into()));
+
+        let index = auction.bidders.iter().position(|&x| x == *winner.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        auction.item_withdrawn = true;
+
+        // whatever the winner escrowed above the clearing price goes back to them
+        let owed = if auction.winning_bid_withdrawn { 0 } else { auction.clearing_price };
+        let refund = auction.deposits[index] - owed;
+        auction.deposits[index] = owed;
+
+        let amount = auction.token_amount;
+
+        if winner_ata.to_account_info().data_is_empty() {
+            create_ata(
+                winner.to_account_info(),
+                winner.to_account_info(),
+                mint.to_account_info(),
+                winner_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            winner_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"clock auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        // must happen after the cpis, see withdraw_item_sealed
+        if refund > 0 {
+            let src = &mut auction.to_account_info();
+            let dst = &mut winner.to_account_info();
+
+            transfer_from_owned_account(src, dst, refund)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn withdraw_winning_bid_clock(ctx: Context<WithdrawWinningBidClock>) -> ProgramResult {
+        let auction: &mut Account<ClockAuction> = &mut ctx.accounts.auction;
+        let owner: &Signer = &ctx.accounts.owner;
+
+        require!(auction.settled, Err(AuctionError::NotSettled.into()));
+        require!(auction.clearing_price > 0, Err(AuctionError::NoWinningBid.into()));
+        require!(!auction.winning_bid_withdrawn, Err(AuctionError::AlreadyWithdrewBid.into()));
+
+        let index = auction.bidders.iter().position(|&x| x == auction.winner)
+            .ok_or(AuctionError::NotBidder)?;
+
+        auction.winning_bid_withdrawn = true;
+
+        let amount = auction.clearing_price;
+        auction.deposits[index] -= amount;
+
+        let src = &mut auction.to_account_info();
+        let dst = &mut owner.to_account_info();
+
+        transfer_from_owned_account(src, dst, amount)?;
+
+        Ok(())
+    }
+
+    pub fn reclaim_item_clock(ctx: Context<ReclaimItemClock>) -> ProgramResult {
+        let auction: &mut Account<ClockAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let owner_ata = &ctx.accounts.owner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        require!(
+            auction.cancelled || (auction.settled && auction.clearing_price == 0),
+            Err(AuctionError::NotSettled.into())
+        );
+        require!(!auction.item_withdrawn, Err(AuctionError::ItemAlreadyReclaimed.into()));
+
+        auction.item_withdrawn = true;
+
+        let amount = auction.token_amount;
+
+        if owner_ata.to_account_info().data_is_empty() {
+            create_ata(
+                owner.to_account_info(),
+                owner.to_account_info(),
+                mint.to_account_info(),
+                owner_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            owner_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"clock auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn create_raffle(
+        ctx: Context<CreateRaffle>,
+        bump: u8,
+        title: String,
+        ticket_price: u64,
+        ticket_cap: u64,
+        min_tickets: u64,
+        start_time: u64,
+        end_time: u64,
+        buyer_cap: u64,
+        token_amount: u64,
+    ) -> ProgramResult {
+        let auction: &mut Account<Raffle> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let owner_ata = &ctx.accounts.owner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(title.chars().count() <= 50, Err(AuctionError::TitleOverflow.into()));
+        require!(ticket_price > 0, Err(AuctionError::InvalidTicketPrice.into()));
+        require!(
+            min_tickets > 0 && min_tickets <= ticket_cap,
+            Err(AuctionError::InvalidMinTickets.into())
+        );
+        require!(token_amount != 0, Err(AuctionError::InvalidTokenAmount.into()));
+        require!(start_time < end_time, Err(AuctionError::InvalidStartTime.into()));
+        require!(cur_time > start_time || start_time == 0, Err(AuctionError::InvalidStartTime.into()));
+        require!(cur_time < end_time, Err(AuctionError::InvalidEndTime.into()));
+
+        auction.owner = *owner.key;
+        auction.mint = mint.key();
+        auction.token_amount = token_amount;
+
+        auction.start_time = if start_time == 0 { cur_time } else { start_time };
+        auction.end_time = end_time;
+        auction.cancelled = false;
+
+        auction.title = title;
+
+        auction.ticket_price = ticket_price;
+        auction.ticket_cap = ticket_cap;
+        auction.min_tickets = min_tickets;
+        auction.tickets_sold = 0;
+
+        auction.buyer_cap = buyer_cap;
+
+        auction.draw_slot = 0;
+        auction.drawn = false;
+
+        auction.proceeds_withdrawn = false;
+        auction.item_withdrawn = false;
+
+        auction.bump = bump;
+
+        create_ata(
+            owner.to_account_info(),
+            auction.to_account_info(),
+            mint.to_account_info(),
+            auction_ata.to_account_info(),
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info()
+        )?;
+
+        transfer_spl(
+            owner.to_account_info(),
+            owner_ata.to_account_info(),
+            auction_ata.to_account_info(),
+            token_amount,
+            token_program.to_account_info(),
+            &[]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn cancel_raffle(ctx: Context<CancelRaffle>) -> ProgramResult {
+        let auction: &mut Account<Raffle> = &mut ctx.accounts.auction;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(cur_time < auction.end_time, Err(AuctionError::CannotCancelAfterClose.into()));
+
+        auction.cancelled = true;
+
+        Ok(())
+    }
+
+    pub fn buy_raffle_tickets(ctx: Context<BuyRaffleTickets>, count: u64) -> ProgramResult {
+        let auction: &mut Account<Raffle> = &mut ctx.accounts.auction;
+        let buyer: &Signer = &ctx.accounts.buyer;
+        let system_program = &ctx.accounts.system_program;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.start_time, Err(AuctionError::BidBeforeStart.into()));
+        require!(cur_time < auction.end_time, Err(AuctionError::BidAfterClose.into()));
+        require!(*buyer.key != auction.owner, Err(AuctionError::OwnerCannotBid.into()));
+        require!(count > 0, Err(AuctionError::InvalidTicketCount.into()));
+        require!(
+            auction.tickets_sold + count <= auction.ticket_cap,
+            Err(AuctionError::TicketCapReached.into())
+        );
+
+        let index = auction.buyers.iter().position(|&x| x == *buyer.key);
+
+        if let Some(i) = index {
+            auction.tickets[i] += count;
+        } else {
+            require!(
+                auction.buyers.len() < (auction.buyer_cap as usize),
+                Err(AuctionError::BidderCapReached.into())
+            );
+            auction.buyers.push(*buyer.key);
+            auction.tickets.push(count);
+        }
+
+        auction.tickets_sold += count;
+
+        let amount = count * auction.ticket_price;
+
+        transfer_sol(
+            buyer.to_account_info(),
+            auction.to_account_info(),
+            amount,
+            system_program.to_account_info()
+        )?;
+
+        Ok(())
+    }
+
+    pub fn commit_raffle_draw(ctx: Context<CommitRaffleDraw>) -> ProgramResult {
+        let auction: &mut Account<Raffle> = &mut ctx.accounts.auction;
+        let slot_hashes = &ctx.accounts.slot_hashes;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
+        require!(!auction.drawn, Err(AuctionError::AlreadyDrawn.into()));
+        require!(
+            auction.tickets_sold >= auction.min_tickets,
+            Err(AuctionError::MinTicketsNotReached.into())
+        );
+
+        // only recommit once the old draw slot was skipped or aged out so nobody can reroll a known hash
+        if auction.draw_slot != 0 {
+            require!(
+                clock.slot > auction.draw_slot && slot_hash_at(slot_hashes, auction.draw_slot)?.is_none(),
+                Err(AuctionError::DrawSlotPending.into())
+            );
+        }
+
+        auction.draw_slot = clock.slot + DRAW_DELAY;
+
+        Ok(())
+    }
+
+    pub fn draw_raffle(ctx: Context<DrawRaffle>) -> ProgramResult {
+        let auction: &mut Account<Raffle> = &mut ctx.accounts.auction;
+        let slot_hashes = &ctx.accounts.slot_hashes;
+
+        let clock: Clock = Clock::get().unwrap();
+
+        require!(!auction.drawn, Err(AuctionError::AlreadyDrawn.into()));
+        require!(auction.draw_slot != 0, Err(AuctionError::DrawNotCommitted.into()));
+        require!(clock.slot > auction.draw_slot, Err(AuctionError::DrawSlotPending.into()));
+
+        let slot_hash = slot_hash_at(slot_hashes, auction.draw_slot)?
+            .ok_or(AuctionError::DrawSlotExpired)?;
+
+        // each ticket is equally likely to win
+        let mut ticket = random_u64(&slot_hash, &auction.key()) % auction.tickets_sold;
+        for (i, count) in auction.tickets.iter().enumerate() {
+            if ticket < *count {
+                auction.winner = auction.buyers[i];
+                break;
+            }
+            ticket -= count;
+        }
+
+        auction.drawn = true;
+
+        Ok(())
+    }
+
+    pub fn reclaim_raffle_tickets(ctx: Context<ReclaimRaffleTickets>) -> ProgramResult {
+        let auction: &mut Account<Raffle> = &mut ctx.accounts.auction;
+        let buyer: &Signer = &ctx.accounts.buyer;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(auction.refunding(cur_time), Err(AuctionError::NotRefunding.into()));
+
+        let index = auction.buyers.iter().position(|&x| x == *buyer.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        let amount = auction.tickets[index] * auction.ticket_price;
+        require!(amount > 0, Err(AuctionError::NothingToWithdraw.into()));
+
+        auction.tickets[index] = 0;
+
+        let src = &mut auction.to_account_info();
+        let dst = &mut buyer.to_account_info();
+
+        transfer_from_owned_account(src, dst, amount)?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_item_raffle(ctx: Context<WithdrawItemRaffle>) -> ProgramResult {
+        let auction: &mut Account<Raffle> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let winner = &ctx.accounts.winner;
+        let winner_ata = &ctx.accounts.winner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        require!(auction.drawn, Err(AuctionError::NotDrawn.into()));
+        require!(!auction.item_withdrawn, Err(AuctionError::ItemAlreadyWithdrawn.into()));
+
+        auction.item_withdrawn = true;
+
+        let amount = auction.token_amount;
+
+        if winner_ata.to_account_info().data_is_empty() {
+            create_ata(
+                winner.to_account_info(),
+                winner.to_account_info(),
+                mint.to_account_info(),
+                winner_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            winner_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"raffle", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_proceeds_raffle(ctx: Context<WithdrawProceedsRaffle>) -> ProgramResult {
+        let auction: &mut Account<Raffle> = &mut ctx.accounts.auction;
+        let owner: &Signer = &ctx.accounts.owner;
+
+        require!(auction.drawn, Err(AuctionError::NotDrawn.into()));
+        require!(!auction.proceeds_withdrawn, Err(AuctionError::AlreadyWithdrewBid.into()));
+
+        auction.proceeds_withdrawn = true;
+
+        let amount = auction.tickets_sold * auction.ticket_price;
+
+        let src = &mut auction.to_account_info();
+        let dst = &mut owner.to_account_info();
+
+        transfer_from_owned_account(src, dst, amount)?;
+
+        Ok(())
+    }
+
+    pub fn reclaim_item_raffle(ctx: Context<ReclaimItemRaffle>) -> ProgramResult {
+        let auction: &mut Account<Raffle> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let owner_ata = &ctx.accounts.owner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(auction.refunding(cur_time), Err(AuctionError::NotRefunding.into()));
+        require!(!auction.item_withdrawn, Err(AuctionError::ItemAlreadyReclaimed.into()));
+
+        auction.item_withdrawn = true;
+
+        let amount = auction.token_amount;
+
+        if owner_ata.to_account_info().data_is_empty() {
+            create_ata(
+                owner.to_account_info(),
+                owner.to_account_info(),
+                mint.to_account_info(),
+                owner_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            owner_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"raffle", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        Ok(())
+    }
+
+    // remaining accounts are a (mint, owner ata, auction ata) triple per lot
+    pub fn create_package_sealed_auction<'info>(
+        ctx: Context<'_, '_, '_, 'info, CreatePackageSealedAuction<'info>>,
+        bump: u8,
+        title: String,
+        floor: u64,
+        start_time: u64,
+        end_time: u64,
+        reveal_period: u64,
+        bidder_cap: u64,
+        lot_amounts: Vec<u64>,
+    ) -> ProgramResult {
+        let auction: &mut Account<PackageSealedAuction> = &mut ctx.accounts.auction;
+        let owner = &ctx.accounts.owner;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(title.chars().count() <= 50, Err(AuctionError::TitleOverflow.into()));
+        require!(
+            !lot_amounts.is_empty() && lot_amounts.len() <= MAX_LOTS,
+            Err(AuctionError::InvalidLots.into())
+        );
+        require!(bidder_cap <= MAX_PACKAGE_BIDS, Err(AuctionError::InvalidPackageBidderCap.into()));
+        require!(start_time < end_time, Err(AuctionError::InvalidStartTime.into()));
+        require!(cur_time > start_time || start_time == 0, Err(AuctionError::InvalidStartTime.into()));
+        require!(cur_time < end_time, Err(AuctionError::InvalidEndTime.into()));
+        require!(reveal_period > end_time, Err(AuctionError::InvalidRevealPeriod.into()));
+        require!(floor > 0, Err(AuctionError::InvalidBidFloor.into()));
+
+        auction.owner = *owner.key;
+
+        auction.start_time = if start_time == 0 { cur_time } else { start_time };
+        auction.end_time = end_time;
+        auction.reveal_period = reveal_period;
+        auction.cancelled = false;
+
+        auction.title = title;
+
+        auction.bidder_cap = bidder_cap;
+        auction.bid_floor = floor;
+
+        auction.settled = false;
+        auction.revenue = 0;
+        auction.proceeds_withdrawn = false;
+        auction.item_reclaimed = false;
+
+        auction.bump = bump;
+
+        auction.lots = escrow_bundle(
+            owner.to_account_info(),
+            auction.to_account_info(),
+            &Pubkey::default(),
+            &lot_amounts,
+            ctx.remaining_accounts,
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info()
+        )?;
+
+        Ok(())
+    }
+
+    pub fn cancel_package_sealed_auction(ctx: Context<CancelPackageSealedAuction>) -> ProgramResult {
+        let auction: &mut Account<PackageSealedAuction> = &mut ctx.accounts.auction;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(cur_time < auction.end_time, Err(AuctionError::CannotCancelRevealPeriod.into()));
+
+        auction.cancelled = true;
+
+        Ok(())
+    }
+
+    pub fn make_package_sealed_bid(
+        ctx: Context<MakePackageSealedBid>,
+        bid_hash: [u8; 32],
+        amount: u64
+    ) -> ProgramResult {
+        let auction: &mut Account<PackageSealedAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+        let system_program = &ctx.accounts.system_program;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.start_time, Err(AuctionError::BidBeforeStart.into()));
+        require!(cur_time < auction.end_time, Err(AuctionError::BidAfterClose.into()));
+        require!(*bidder.key != auction.owner, Err(AuctionError::OwnerCannotBid.into()));
+        require!(amount > 0, Err(AuctionError::MustSendSol.into()));
+
+        let index = auction.bidders.iter().position(|&x| x == *bidder.key);
+
+        require!(index.is_none(), Err(AuctionError::DuplicateSealedBid.into()));
+        require!(
+            auction.bidders.len() < (auction.bidder_cap as usize),
+            Err(AuctionError::BidderCapReached.into())
+        );
+
+        auction.bidders.push(*bidder.key);
+        auction.sealed_bids.push(bid_hash);
+        auction.fake_bids.push(amount);
+        auction.packages.push(0);
+        auction.bids.push(0);
+        auction.winners.push(false);
+
+        transfer_sol(
+            bidder.to_account_info(),
+            auction.to_account_info(),
+            amount,
+            system_program.to_account_info()
+        )?;
+
+        Ok(())
+    }
+
+    pub fn reclaim_package_sealed_bid(ctx: Context<ReclaimPackageSealedBid>) -> ProgramResult {
+        let auction: &mut Account<PackageSealedAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        // after bidding closes, refunds go through settlement
+        require!(
+            cur_time < auction.end_time || auction.cancelled,
+            Err(AuctionError::BidAfterClose.into())
+        );
+
+        let index = auction.bidders.iter().position(|&x| x == *bidder.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        let fake_bid = auction.fake_bids[index];
+
+        auction.bidders.remove(index);
+        auction.sealed_bids.remove(index);
+        auction.fake_bids.remove(index);
+        auction.packages.remove(index);
+        auction.bids.remove(index);
+        auction.winners.remove(index);
+
+        let src = &mut auction.to_account_info();
+        let dst = &mut bidder.to_account_info();
+
+        transfer_from_owned_account(src, dst, fake_bid)?;
+
+        Ok(())
+    }
+
+    pub fn reveal_package_sealed_bid(
+        ctx: Context<RevealPackageSealedBid>,
+        package: u8,
+        bid: u64,
+        salt: [u8; 32]
+    ) -> ProgramResult {
+        let auction: &mut Account<PackageSealedAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
+        require!(cur_time < auction.reveal_period, Err(AuctionError::RevealPeriodOver.into()));
+
+        let index = auction.bidders.iter().position(|&x| x == *bidder.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        let fake_bid = auction.fake_bids[index];
+
+        let bid_hash = auction.sealed_bids[index];
+        let proposed_hash = compute_package_bid_hash(&auction.key(), bidder.key, package, bid, &salt);
+
+        require!(bid_hash == proposed_hash, Err(AuctionError::HashMismatch.into()));
+        require!(
+            package != 0 && (package as usize) < (1 << auction.lots.len()),
+            Err(AuctionError::InvalidPackage.into())
+        );
+        require!(bid > auction.bid_floor, Err(AuctionError::UnderBidFloor.into()));
+        require!(bid <= fake_bid, Err(AuctionError::InsufficientSol.into()));
+
+        auction.packages[index] = package;
+        auction.bids[index] = bid;
+
+        Ok(())
+    }
+
+    pub fn settle_package_sealed_auction(ctx: Context<SettlePackageSealedAuction>) -> ProgramResult {
+        let auction: &mut Account<PackageSealedAuction> = &mut ctx.accounts.auction;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.reveal_period, Err(AuctionError::RevealPeriodNotOver.into()));
+        require!(!auction.settled, Err(AuctionError::AlreadySettled.into()));
+
+        let winners = select_packages(auction.lots.len(), &auction.packages, &auction.bids);
+
+        // winning bidders pay their own bid
+        auction.revenue = winners.iter().zip(auction.bids.iter())
+            .filter(|&(won, _)| *won)
+            .map(|(_, bid)| bid)
+            .sum();
+        auction.winners = winners;
+        auction.settled = true;
+
+        Ok(())
+    }
+
+    // winners pass a (mint, auction ata, bidder ata) triple for each lot in their package, in lot order
+    pub fn withdraw_package_sealed<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawPackageSealed<'info>>
+    ) -> ProgramResult {
+        let auction: &mut Account<PackageSealedAuction> = &mut ctx.accounts.auction;
+        let bidder = &ctx.accounts.bidder;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        require!(auction.settled, Err(AuctionError::NotSettled.into()));
+
+        let i = auction.bidders.iter().position(|&x| x == *bidder.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        let fake_bid = auction.fake_bids[i];
+        require!(fake_bid > 0, Err(AuctionError::NothingToWithdraw.into()));
+
+        let won = auction.winners[i];
+        let payment = if won { auction.bids[i] } else { 0 };
+        let refund = fake_bid - payment;
+
+        auction.fake_bids[i] = 0;
+
+        if won {
+            let lots = auction.package_lots(auction.packages[i]);
+
+            release_bundle(
+                bidder.to_account_info(),
+                auction.to_account_info(),
+                &lots,
+                ctx.remaining_accounts,
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info(),
+                &[&[b"package sealed auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+            )?;
+        }
+
+        // refund difference between sent SOL and the winning bid
+        // must happen last, see withdraw_item_sealed
+        if refund > 0 {
+            let src = &mut auction.to_account_info();
+            let dst = &mut bidder.to_account_info();
+            transfer_from_owned_account(src, dst, refund)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn withdraw_proceeds_package_sealed(ctx: Context<WithdrawProceedsPackageSealed>) -> ProgramResult {
+        let auction: &mut Account<PackageSealedAuction> = &mut ctx.accounts.auction;
+        let owner: &Signer = &ctx.accounts.owner;
+
+        require!(auction.settled, Err(AuctionError::NotSettled.into()));
+        require!(auction.revenue > 0, Err(AuctionError::NoWinningBid.into()));
+        require!(
+            !auction.proceeds_withdrawn,
+            Err(AuctionError::AlreadyWithdrewBid.into())
+        );
+
+        auction.proceeds_withdrawn = true;
+
+        let amount = auction.revenue;
+
+        let src = &mut auction.to_account_info();
+        let dst = &mut owner.to_account_info();
+
+        transfer_from_owned_account(src, dst, amount)?;
+
+        Ok(())
+    }
+
+    // remaining accounts are a (mint, auction ata, owner ata) triple for each unsold lot, in lot order
+    pub fn reclaim_item_package_sealed<'info>(
+        ctx: Context<'_, '_, '_, 'info, ReclaimItemPackageSealed<'info>>
+    ) -> ProgramResult {
+        let auction: &mut Account<PackageSealedAuction> = &mut ctx.accounts.auction;
+        let owner = &ctx.accounts.owner;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        require!(
+            auction.settled || auction.cancelled,
+            Err(AuctionError::NotSettled.into())
+        );
+        require!(!auction.item_reclaimed, Err(AuctionError::ItemAlreadyReclaimed.into()));
+
+        let sold: u8 = auction.winners.iter().zip(auction.packages.iter())
+            .filter(|&(won, _)| *won)
+            .fold(0, |sold, (_, package)| sold | package);
+        let unsold = auction.package_lots(!sold);
+        require!(!unsold.is_empty(), Err(AuctionError::NothingToWithdraw.into()));
+
+        auction.item_reclaimed = true;
+
+        release_bundle(
+            owner.to_account_info(),
+            auction.to_account_info(),
+            &unsold,
+            ctx.remaining_accounts,
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info(),
+            &[&[b"package sealed auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn create_catalog_auction(
+        ctx: Context<CreateCatalogAuction>,
+        bump: u8,
+        title: String,
+        floor: u64,
+        increment: u64,
+        start_time: u64,
+        end_time: u64,
+        bidder_cap: u64,
+        lot_cap: u64,
+        lot_bidder_cap: u64,
+    ) -> ProgramResult {
+        let auction: &mut Account<CatalogAuction> = &mut ctx.accounts.auction;
+        let owner = &ctx.accounts.owner;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(title.chars().count() <= 50, Err(AuctionError::TitleOverflow.into()));
+        require!(increment != 0, Err(AuctionError::InvalidIncrement.into()));
+        require!(lot_cap != 0, Err(AuctionError::InvalidLotCap.into()));
+        require!(
+            CatalogAuction::space(lot_cap, bidder_cap, lot_bidder_cap) <= MAX_INIT_ACCOUNT_SIZE,
+            Err(AuctionError::CatalogTooLarge.into())
+        );
+        require!(start_time < end_time, Err(AuctionError::InvalidStartTime.into()));
+        require!(cur_time > start_time || start_time == 0, Err(AuctionError::InvalidStartTime.into()));
+        require!(cur_time < end_time, Err(AuctionError::InvalidEndTime.into()));
+        require!(floor > 0, Err(AuctionError::InvalidBidFloor.into()));
+
+        auction.owner = *owner.key;
+
+        auction.start_time = if start_time == 0 { cur_time } else { start_time };
+        auction.end_time = end_time;
+        auction.cancelled = false;
+
+        auction.title = title;
+
+        auction.lot_cap = lot_cap;
+
+        auction.bidder_cap = bidder_cap;
+        auction.lot_bidder_cap = lot_bidder_cap;
+        auction.bid_floor = floor;
+        auction.min_bid_increment = increment;
+
+        auction.bump = bump;
+
+        Ok(())
+    }
+
+    // lots are escrowed one per instruction since a catalog has more of them than fit in one transaction
+    pub fn add_catalog_lot(ctx: Context<AddCatalogLot>, token_amount: u64) -> ProgramResult {
+        let auction: &mut Account<CatalogAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let owner_ata = &ctx.accounts.owner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(auction.bidders.is_empty(), Err(AuctionError::LotsLocked.into()));
+        require!(
+            auction.lots.len() < (auction.lot_cap as usize),
+            Err(AuctionError::LotCapReached.into())
+        );
+        require!(token_amount != 0, Err(AuctionError::InvalidTokenAmount.into()));
+        require!(
+            !auction.lots.iter().any(|lot| lot.mint == mint.key()),
+            Err(AuctionError::DuplicateBundleMint.into())
+        );
+        require!(
+            *auction_ata.key == spl_associated_token_account::get_associated_token_address(&auction.key(), &mint.key()),
+            Err(AuctionError::InvalidAuctionAta.into())
+        );
+
+        auction.lots.push(CatalogLot {
+            mint: mint.key(),
+            amount: token_amount,
+            highest_bidder: Pubkey::default(),
+            highest_bid: 0,
+            item_withdrawn: false,
+            winning_bid_withdrawn: false,
+            bids: Vec::new(),
+        });
+
+        create_ata(
+            owner.to_account_info(),
+            auction.to_account_info(),
+            mint.to_account_info(),
+            auction_ata.to_account_info(),
+            token_program.to_account_info(),
+            ata_program.to_account_info(),
+            system_program.to_account_info(),
+            rent_sysvar.to_account_info()
+        )?;
+
+        transfer_spl(
+            owner.to_account_info(),
+            owner_ata.to_account_info(),
+            auction_ata.to_account_info(),
+            token_amount,
+            token_program.to_account_info(),
+            &[]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn cancel_catalog_auction(ctx: Context<CancelCatalogAuction>) -> ProgramResult {
+        let auction: &mut Account<CatalogAuction> = &mut ctx.accounts.auction;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(cur_time < auction.end_time, Err(AuctionError::CannotCancelAfterClose.into()));
+
+        auction.cancelled = true;
+
+        Ok(())
+    }
+
+    pub fn make_catalog_bid(ctx: Context<MakeCatalogBid>, lot: u64, amount: u64) -> ProgramResult {
+        let auction: &mut Account<CatalogAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+        let system_program = &ctx.accounts.system_program;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.start_time, Err(AuctionError::BidBeforeStart.into()));
+        require!(cur_time < auction.end_time, Err(AuctionError::BidAfterClose.into()));
+        require!(*bidder.key != auction.owner, Err(AuctionError::OwnerCannotBid.into()));
+
+        let lot_index = lot as usize;
+        require!(lot_index < auction.lots.len(), Err(AuctionError::InvalidLot.into()));
+
+        let index = match auction.bidders.iter().position(|&x| x == *bidder.key) {
+            Some(i) => i,
+            None => {
+                require!(
+                    auction.bidders.len() < (auction.bidder_cap as usize),
+                    Err(AuctionError::BidderCapReached.into())
+                );
+                auction.bidders.push(*bidder.key);
+                auction.bidders.len() - 1
+            }
+        };
+
+        let lot_bidder_cap = auction.lot_bidder_cap as usize;
+        let bid_floor = auction.bid_floor;
+        let min_bid_increment = auction.min_bid_increment;
+        let lot_entry = &mut auction.lots[lot_index];
+        let slot = match lot_entry.bid_position(index) {
+            Some(i) => i,
+            None => {
+                require!(
+                    lot_entry.bids.len() < lot_bidder_cap,
+                    Err(AuctionError::LotBidderCapReached.into())
+                );
+                lot_entry.bids.push(CatalogBid { bidder_index: index as u32, bid: 0 });
+                lot_entry.bids.len() - 1
+            }
+        };
+
+        // new amount plus what's already escrowed on this lot
+        let total_bid = lot_entry.bids[slot].bid + amount;
+
+        require!(
+            total_bid > bid_floor,
+            Err(AuctionError::UnderBidFloor.into())
+        );
+        require!(
+            total_bid > (lot_entry.highest_bid + min_bid_increment),
+            Err(AuctionError::InsufficientBid.into())
+        );
+
+        lot_entry.bids[slot].bid = total_bid;
+        lot_entry.highest_bidder = *bidder.key;
+        lot_entry.highest_bid = total_bid;
+
+        transfer_sol(
+            bidder.to_account_info(),
+            auction.to_account_info(),
+            amount,
+            system_program.to_account_info()
+        )?;
+
+        Ok(())
+    }
+
+    pub fn reclaim_catalog_bid(ctx: Context<ReclaimCatalogBid>, lot: u64) -> ProgramResult {
+        let auction: &mut Account<CatalogAuction> = &mut ctx.accounts.auction;
+        let bidder: &Signer = &ctx.accounts.bidder;
+
+        let lot_index = lot as usize;
+        require!(lot_index < auction.lots.len(), Err(AuctionError::InvalidLot.into()));
+
+        let index = auction.bidders.iter().position(|&x| x == *bidder.key)
+            .ok_or(AuctionError::NotBidder)?;
+
+        require!(
+            *bidder.key != auction.lots[lot_index].highest_bidder || auction.cancelled,
+            Err(AuctionError::WinnerCannotWithdrawBid.into())
+        );
+
+        // dropping the entry frees its slot on the lot for another bidder
+        let slot = auction.lots[lot_index].bid_position(index)
+            .ok_or(AuctionError::NothingToWithdraw)?;
+        let bid = auction.lots[lot_index].bids.remove(slot).bid;
+
+        let src = &mut auction.to_account_info();
+        let dst = &mut bidder.to_account_info();
+
+        transfer_from_owned_account(src, dst, bid)?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_item_catalog(ctx: Context<WithdrawItemCatalog>, lot: u64) -> ProgramResult {
+        let auction: &mut Account<CatalogAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let winner = &ctx.accounts.highest_bidder;
+        let winner_ata = &ctx.accounts.highest_bidder_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
+
+        let lot_index = lot as usize;
+        require!(lot_index < auction.lots.len(), Err(AuctionError::InvalidLot.into()));
+        // every lot's ata belongs to the auction so make sure this one matches the lot
+        require!(
+            mint.key() == auction.lots[lot_index].mint &&
+            *auction_ata.key == spl_associated_token_account::get_associated_token_address(&auction.key(), &mint.key()),
+            Err(AuctionError::InvalidLot.into())
+        );
+        require!(
+            auction.lots[lot_index].highest_bid > 0 && *winner.key == auction.lots[lot_index].highest_bidder,
+            Err(AuctionError::NoWinningBid.into())
+        );
+        require!(
+            !auction.lots[lot_index].item_withdrawn,
+            Err(AuctionError::ItemAlreadyWithdrawn.into())
+        );
+
+        auction.lots[lot_index].item_withdrawn = true;
+
+        let amount = auction.lots[lot_index].amount;
+
+        if winner_ata.to_account_info().data_is_empty() {
+            create_ata(
+                winner.to_account_info(),
+                winner.to_account_info(),
+                mint.to_account_info(),
+                winner_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            winner_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"catalog auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        Ok(())
+    }
+
+    pub fn withdraw_winning_bid_catalog(ctx: Context<WithdrawWinningBidCatalog>, lot: u64) -> ProgramResult {
+        let auction: &mut Account<CatalogAuction> = &mut ctx.accounts.auction;
+        let owner: &Signer = &ctx.accounts.owner;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.cancelled, Err(AuctionError::AuctionCancelled.into()));
+        require!(cur_time > auction.end_time, Err(AuctionError::AuctionNotOver.into()));
+
+        let lot_index = lot as usize;
+        require!(lot_index < auction.lots.len(), Err(AuctionError::InvalidLot.into()));
+
+        let winning_bid = auction.lots[lot_index].highest_bid;
+        let index = auction.bidders.iter().position(|&x| x == auction.lots[lot_index].highest_bidder)
+            .ok_or(AuctionError::NoWinningBid)?;
+
+        require!(
+            !auction.lots[lot_index].winning_bid_withdrawn,
+            Err(AuctionError::AlreadyWithdrewBid.into())
+        );
+
+        auction.lots[lot_index].winning_bid_withdrawn = true;
+        // the winner's escrow on this lot is exactly the winning bid
+        let slot = auction.lots[lot_index].bid_position(index)
+            .ok_or(AuctionError::NoWinningBid)?;
+        auction.lots[lot_index].bids.remove(slot);
+
+        let src = &mut auction.to_account_info();
+        let dst = &mut owner.to_account_info();
+
+        transfer_from_owned_account(src, dst, winning_bid)?;
+
+        Ok(())
+    }
+
+    pub fn reclaim_item_catalog(ctx: Context<ReclaimItemCatalog>, lot: u64) -> ProgramResult {
+        let auction: &mut Account<CatalogAuction> = &mut ctx.accounts.auction;
+        let auction_ata = &ctx.accounts.auction_ata;
+        let owner = &ctx.accounts.owner;
+        let owner_ata = &ctx.accounts.owner_ata;
+        let mint = &ctx.accounts.mint;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        let lot_index = lot as usize;
+        require!(lot_index < auction.lots.len(), Err(AuctionError::InvalidLot.into()));
+        // every lot's ata belongs to the auction so make sure this one matches the lot
+        require!(
+            mint.key() == auction.lots[lot_index].mint &&
+            *auction_ata.key == spl_associated_token_account::get_associated_token_address(&auction.key(), &mint.key()),
+            Err(AuctionError::InvalidLot.into())
+        );
+        require!(
+            (auction.lots[lot_index].highest_bid == 0 && cur_time > auction.end_time) || auction.cancelled,
+            Err(AuctionError::AuctionNotOver.into())
+        );
+        require!(
+            !auction.lots[lot_index].item_withdrawn,
+            Err(AuctionError::ItemAlreadyReclaimed.into())
+        );
+
+        auction.lots[lot_index].item_withdrawn = true;
+
+        let amount = auction.lots[lot_index].amount;
+
+        if owner_ata.to_account_info().data_is_empty() {
+            create_ata(
+                owner.to_account_info(),
+                owner.to_account_info(),
+                mint.to_account_info(),
+                owner_ata.to_account_info(),
+                token_program.to_account_info(),
+                ata_program.to_account_info(),
+                system_program.to_account_info(),
+                rent_sysvar.to_account_info()
+            )?;
+        }
+
+        transfer_spl(
+            auction.to_account_info(),
+            auction_ata.to_account_info(),
+            owner_ata.to_account_info(),
+            amount,
+            token_program.to_account_info(),
+            &[&[b"catalog auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+        )?;
+
+        Ok(())
+    }
+
+    // the mint's authority has to be set to the auction pda before creating the series
+    pub fn create_series_auction(
+        ctx: Context<CreateSeriesAuction>,
+        bump: u8,
+        config_bump: u8,
+        title: String,
+        floor: u64,
+        increment: u64,
+        round_duration: u64,
+        start_time: u64,
+        token_amount: u64,
+    ) -> ProgramResult {
+        let auction: &mut Account<SeriesAuction> = &mut ctx.accounts.auction;
+        let config: &mut Account<SeriesConfig> = &mut ctx.accounts.config;
+        let owner = &ctx.accounts.owner;
+        let mint = &ctx.accounts.mint;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(title.chars().count() <= 50, Err(AuctionError::TitleOverflow.into()));
+        require!(increment != 0, Err(AuctionError::InvalidIncrement.into()));
+        require!(round_duration != 0, Err(AuctionError::InvalidRoundDuration.into()));
+        require!(token_amount != 0, Err(AuctionError::InvalidTokenAmount.into()));
+        require!(floor > 0, Err(AuctionError::InvalidBidFloor.into()));
+        require!(cur_time > start_time || start_time == 0, Err(AuctionError::InvalidStartTime.into()));
+        require!(
+            mint.mint_authority == COption::Some(auction.key()),
+            Err(AuctionError::InvalidMintAuthority.into())
+        );
+
+        auction.owner = *owner.key;
+        auction.mint = mint.key();
+        auction.token_amount = token_amount;
+
+        auction.title = title;
+        auction.config = config.key();
+
+        config.auction = auction.key();
+        config.round_duration = round_duration;
+        config.bid_floor = floor;
+        config.min_bid_increment = increment;
+        config.bump = config_bump;
+
+        let first_start = if start_time == 0 { cur_time } else { start_time };
+        auction.round = 0;
+        auction.round_start = first_start;
+        auction.round_end = first_start + round_duration;
+        auction.round_settled = false;
+        auction.stopped = false;
+
+        auction.highest_bid = 0;
+
+        auction.bump = bump;
+
+        Ok(())
+    }
+
+    pub fn stop_series_auction(ctx: Context<StopSeriesAuction>) -> ProgramResult {
+        let auction: &mut Account<SeriesAuction> = &mut ctx.accounts.auction;
+
+        require!(!auction.stopped, Err(AuctionError::SeriesStopped.into()));
+
+        auction.stopped = true;
+
+        Ok(())
+    }
+
+    pub fn make_series_bid(ctx: Context<MakeSeriesBid>, amount: u64) -> ProgramResult {
+        let auction: &mut Account<SeriesAuction> = &mut ctx.accounts.auction;
+        let config = &ctx.accounts.config;
+        let bidder: &Signer = &ctx.accounts.bidder;
+        let previous_bidder = &ctx.accounts.previous_bidder;
+        let system_program = &ctx.accounts.system_program;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.round_settled, Err(AuctionError::SeriesStopped.into()));
+        require!(cur_time > auction.round_start, Err(AuctionError::BidBeforeStart.into()));
+        require!(cur_time < auction.round_end, Err(AuctionError::BidAfterClose.into()));
+        require!(*bidder.key != auction.owner, Err(AuctionError::OwnerCannotBid.into()));
+
+        // bids aren't topped up since the previous one is refunded right away
+        require!(amount > config.bid_floor, Err(AuctionError::UnderBidFloor.into()));
+        require!(
+            amount > (auction.highest_bid + config.min_bid_increment),
+            Err(AuctionError::InsufficientBid.into())
+        );
+
+        let refund = auction.highest_bid;
+        if refund > 0 {
+            require!(
+                *previous_bidder.key == auction.highest_bidder,
+                Err(AuctionError::HighestBidderMismatch.into())
+            );
+        }
+
+        auction.highest_bidder = *bidder.key;
+        auction.highest_bid = amount;
+
+        transfer_sol(
+            bidder.to_account_info(),
+            auction.to_account_info(),
+            amount,
+            system_program.to_account_info()
+        )?;
+
+        // must happen after the cpis, see withdraw_item_sealed
+        if refund > 0 {
+            let src = &mut auction.to_account_info();
+            let dst = &mut previous_bidder.to_account_info();
+            transfer_from_owned_account(src, dst, refund)?;
+        }
+
+        Ok(())
+    }
+
+    // permissionless, mints to the winner, pays the owner, and starts the next round
+    pub fn settle_series_round(ctx: Context<SettleSeriesRound>) -> ProgramResult {
+        let auction: &mut Account<SeriesAuction> = &mut ctx.accounts.auction;
+        let config = &ctx.accounts.config;
+        let owner = &ctx.accounts.owner;
+        let mint = &ctx.accounts.mint;
+        let winner = &ctx.accounts.highest_bidder;
+        let winner_ata = &ctx.accounts.highest_bidder_ata;
+        let payer = &ctx.accounts.payer;
+        let token_program = &ctx.accounts.token_program;
+        let ata_program = &ctx.accounts.ata_program;
+        let system_program = &ctx.accounts.system_program;
+        let rent_sysvar = &ctx.accounts.rent_sysvar;
+
+        let clock: Clock = Clock::get().unwrap();
+        let cur_time: u64 = clock.unix_timestamp as u64;
+
+        require!(!auction.round_settled, Err(AuctionError::AlreadySettled.into()));
+        require!(cur_time > auction.round_end, Err(AuctionError::AuctionNotOver.into()));
+
+        let winning_bid = auction.highest_bid;
+
+        if winning_bid > 0 {
+            require!(
+                *winner.key == auction.highest_bidder,
+                Err(AuctionError::HighestBidderMismatch.into())
+            );
+            // the settler is untrusted, so the tokens can only go to the winner's own ata
+            require!(
+                *winner_ata.key == spl_associated_token_account::get_associated_token_address(&auction.highest_bidder, &auction.mint),
+                Err(AuctionError::InvalidWinnerAta.into())
+            );
+
+            if winner_ata.to_account_info().data_is_empty() {
+                create_ata(
+                    payer.to_account_info(),
+                    winner.to_account_info(),
+                    mint.to_account_info(),
+                    winner_ata.to_account_info(),
+                    token_program.to_account_info(),
+                    ata_program.to_account_info(),
+                    system_program.to_account_info(),
+                    rent_sysvar.to_account_info()
+                )?;
+            }
+
+            mint_spl(
+                mint.to_account_info(),
+                winner_ata.to_account_info(),
+                auction.to_account_info(),
+                auction.token_amount,
+                token_program.to_account_info(),
+                &[&[b"series auction", auction.owner.as_ref(), name_seed(&auction.title), &[auction.bump]]]
+            )?;
+        }
+
+        // a round with no bids just rolls over, so a stopped series won't start another one
+        if auction.stopped {
+            auction.round_settled = true;
+        } else {
+            auction.round += 1;
+            auction.round_start = cur_time;
+            auction.round_end = cur_time + config.round_duration;
+        }
+        auction.highest_bidder = Pubkey::default();
+        auction.highest_bid = 0;
+
+        // must happen after the cpis, see withdraw_item_sealed
+        if winning_bid > 0 {
+            let src = &mut auction.to_account_info();
+            let dst = &mut owner.to_account_info();
+            transfer_from_owned_account(src, dst, winning_bid)?;
+        }
+
+        Ok(())
+    }
+}
+
+// checks a reveal against the stored commitment once the caller has hashed it for the auction's version
+fn reveal_sealed(
+    auction: &mut Account<SealedAuction>,
+    bidder: &AccountInfo,
+    bid: u64,
+    proposed_hash: [u8; 32]
+) -> ProgramResult {
+    let clock: Clock = Clock::get().unwrap();
+    let cur_time: u64 = clock.unix_timestamp as u64;
+
+    auction.require_phase(cur_time, &[Phase::Revealing])?;
+
+    let index = auction.bidders.iter().position(|&x| x == *bidder.key);
+
+    if let None = index {
+        return Err(AuctionError::NotBidder.into())
+    } else {
+        let fake_bid = auction.fake_bids[index.unwrap()];
+
+        let bid_hash = auction.sealed_bids[index.unwrap()];
+
+        require!(!auction.revealed[index.unwrap()], Err(AuctionError::BidAlreadyRevealed.into()));
+        require!(bid_hash == proposed_hash, Err(AuctionError::HashMismatch.into()));
+        require!(bid > auction.bid_floor, Err(AuctionError::UnderBidFloor.into()));
+        require!(fake_bid >= bid, Err(AuctionError::InsufficientSol.into()));
+        require!(
+            auction.fits_deposit_tier(bid, fake_bid),
+            Err(AuctionError::BidOutsideDepositTier.into())
+        );
+
+        let commit_time = auction.commit_times[index.unwrap()];
+        let tie_key = tie_break_key(auction.tie_break, commit_time, cur_time);
+
+        // only the leader's deposit can still be needed, everyone below it is refunded now
+        // and kept in the book for pricing, a random tie for the lead is kept until the draw
+        let rank = auction.record_reveal(RevealedBid {
+            bidder: *bidder.key,
+            bid,
+            commit_time,
+            reveal_time: cur_time,
+            tie_key,
+        });
+
+        if rank == 0 || (auction.tie_break == TieBreak::Random && bid == auction.highest_bid) {
+            auction.revealed[index.unwrap()] = true;
+        } else {
+            auction.bidders.remove(index.unwrap());
+            auction.sealed_bids.remove(index.unwrap());
+            auction.fake_bids.remove(index.unwrap());
+            auction.revealed.remove(index.unwrap());
+            auction.commit_times.remove(index.unwrap());
+            auction.ciphertexts.remove(index.unwrap());
+
+            let src = &mut auction.to_account_info();
+            let dst = &mut bidder.to_account_info();
+
+            transfer_from_owned_account(src, dst, fake_bid)?;
+        }
+    }
+
+    Ok(())
 }
\ No newline at end of file
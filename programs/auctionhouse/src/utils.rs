@@ -2,9 +2,13 @@ use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{
     system_instruction::transfer,
     program::invoke,
-    program::invoke_signed
+    program::invoke_signed,
+    instruction::Instruction,
+    ed25519_program
 };
 use tiny_keccak::{Hasher, Keccak};
+use crate::account::{BundleItem, ClearingRule, DecayCurve, TieBreak};
+use crate::error::AuctionError;
 
 pub fn create_ata<'info>(
     payer: AccountInfo<'info>,
@@ -69,6 +73,150 @@ pub fn transfer_spl<'info>(
     Ok(())
 }
 
+// escrow the extra mints of a bundled lot
+// remaining accounts are a (mint, owner ata, auction ata) triple per bundle amount
+pub fn escrow_bundle<'info>(
+    owner: AccountInfo<'info>,
+    auction: AccountInfo<'info>,
+    lot_mint: &Pubkey,
+    amounts: &[u64],
+    remaining_accounts: &[AccountInfo<'info>],
+    token_program: AccountInfo<'info>,
+    ata_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent_sysvar: AccountInfo<'info>,
+) -> Result<Vec<BundleItem>, ProgramError> {
+    if remaining_accounts.len() != amounts.len() * 3 {
+        return Err(AuctionError::InvalidBundleAccounts.into());
+    }
+
+    let mut bundle: Vec<BundleItem> = Vec::with_capacity(amounts.len());
+    for (accounts, &amount) in remaining_accounts.chunks(3).zip(amounts.iter()) {
+        let mint = &accounts[0];
+        let owner_ata = &accounts[1];
+        let auction_ata = &accounts[2];
+
+        if amount == 0 {
+            return Err(AuctionError::InvalidTokenAmount.into());
+        }
+        if mint.key == lot_mint || bundle.iter().any(|item| item.mint == *mint.key) {
+            return Err(AuctionError::DuplicateBundleMint.into());
+        }
+        if *auction_ata.key != spl_associated_token_account::get_associated_token_address(auction.key, mint.key) {
+            return Err(AuctionError::InvalidBundleAccounts.into());
+        }
+
+        create_ata(
+            owner.clone(),
+            auction.clone(),
+            mint.clone(),
+            auction_ata.clone(),
+            token_program.clone(),
+            ata_program.clone(),
+            system_program.clone(),
+            rent_sysvar.clone()
+        )?;
+
+        transfer_spl(
+            owner.clone(),
+            owner_ata.clone(),
+            auction_ata.clone(),
+            amount,
+            token_program.clone(),
+            &[]
+        )?;
+
+        bundle.push(BundleItem { mint: *mint.key, amount });
+    }
+
+    Ok(bundle)
+}
+
+// move every extra mint of a bundled lot out of escrow to the wallet's atas
+// remaining accounts are a (mint, auction ata, wallet ata) triple per bundle item
+pub fn release_bundle<'info>(
+    wallet: AccountInfo<'info>,
+    auction: AccountInfo<'info>,
+    bundle: &[BundleItem],
+    remaining_accounts: &[AccountInfo<'info>],
+    token_program: AccountInfo<'info>,
+    ata_program: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    rent_sysvar: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]]
+) -> ProgramResult {
+    if remaining_accounts.len() != bundle.len() * 3 {
+        return Err(AuctionError::InvalidBundleAccounts.into());
+    }
+
+    for (accounts, item) in remaining_accounts.chunks(3).zip(bundle.iter()) {
+        let mint = &accounts[0];
+        let auction_ata = &accounts[1];
+        let wallet_ata = &accounts[2];
+
+        if *mint.key != item.mint ||
+            *auction_ata.key != spl_associated_token_account::get_associated_token_address(auction.key, mint.key) ||
+            *wallet_ata.key != spl_associated_token_account::get_associated_token_address(wallet.key, mint.key) {
+            return Err(AuctionError::InvalidBundleAccounts.into());
+        }
+
+        if wallet_ata.data_is_empty() {
+            create_ata(
+                wallet.clone(),
+                wallet.clone(),
+                mint.clone(),
+                wallet_ata.clone(),
+                token_program.clone(),
+                ata_program.clone(),
+                system_program.clone(),
+                rent_sysvar.clone()
+            )?;
+        }
+
+        transfer_spl(
+            auction.clone(),
+            auction_ata.clone(),
+            wallet_ata.clone(),
+            item.amount,
+            token_program.clone(),
+            signer_seeds
+        )?;
+    }
+
+    Ok(())
+}
+
+// mint signed by a program pda that holds the mint authority
+pub fn mint_spl<'info>(
+    mint: AccountInfo<'info>,
+    dst_ata: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    amount: u64,
+    token_program: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]]
+) -> ProgramResult {
+
+    invoke_signed(
+        &spl_token::instruction::mint_to(
+            &token_program.key(),
+            &mint.key(),
+            &dst_ata.key(),
+            &authority.key(),
+            &[],
+            amount,
+        )?,
+        &[
+            mint.to_account_info(),
+            dst_ata.to_account_info(),
+            authority.to_account_info(),
+            token_program.to_account_info()
+        ],
+        signer_seeds,
+    )?;
+
+    Ok(())
+}
+
 // transfer from system-owned account
 pub fn transfer_sol<'info>(
     src: AccountInfo<'info>,
@@ -109,6 +257,9 @@ pub fn transfer_from_owned_account(
     Ok(())
 }
 
+pub const BID_COMMITMENT_DOMAIN: &[u8] = b"auctionhouse sealed bid v2";
+pub const REVEAL_MESSAGE_DOMAIN: &[u8] = b"auctionhouse sealed reveal";
+
 pub fn compute_bid_hash(bid: u64, nonce: u64) -> [u8; 32] {
     let mut new_hash = [0u8; 32];
     let mut hasher = Keccak::v256();
@@ -118,6 +269,318 @@ pub fn compute_bid_hash(bid: u64, nonce: u64) -> [u8; 32] {
     new_hash
 }
 
+// fixed-width fields bound to the auction and bidder, so a commitment can't be read two ways
+// or replayed in another auction
+pub fn compute_bid_hash_v2(auction: &Pubkey, bidder: &Pubkey, bid: u64, salt: &[u8; 32]) -> [u8; 32] {
+    let mut new_hash = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(BID_COMMITMENT_DOMAIN);
+    hasher.update(auction.as_ref());
+    hasher.update(bidder.as_ref());
+    hasher.update(&bid.to_le_bytes());
+    hasher.update(salt);
+    hasher.finalize(&mut new_hash);
+    new_hash
+}
+
+// what a bidder signs off-chain so a relayer can reveal for them
+pub fn reveal_message(auction: &Pubkey, bid: u64, salt: &[u8; 32]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(REVEAL_MESSAGE_DOMAIN.len() + 32 + 8 + 32);
+    message.extend_from_slice(REVEAL_MESSAGE_DOMAIN);
+    message.extend_from_slice(auction.as_ref());
+    message.extend_from_slice(&bid.to_le_bytes());
+    message.extend_from_slice(salt);
+    message
+}
+
+// the ed25519 program has already checked the signature by the time we run, so this only
+// makes sure it covered the right message and key, all stored in the instruction's own data
+pub fn verify_ed25519_instruction(ix: &Instruction, signer: &Pubkey, message: &[u8]) -> bool {
+    // one signature count byte and a padding byte, then seven u16 offsets
+    let data = &ix.data;
+    if ix.program_id != ed25519_program::ID || data.len() < 16 || data[0] != 1 {
+        return false;
+    }
+
+    let offsets: Vec<usize> = (0..7)
+        .map(|i| u16::from_le_bytes([data[2 + 2 * i], data[3 + 2 * i]]) as usize)
+        .collect();
+    let (signature_ix, key_offset, key_ix, message_offset, message_size, message_ix) =
+        (offsets[1], offsets[2], offsets[3], offsets[4], offsets[5], offsets[6]);
+
+    // u16::MAX points at the ed25519 instruction itself rather than some other instruction
+    let own_data = u16::MAX as usize;
+    if signature_ix != own_data || key_ix != own_data || message_ix != own_data {
+        return false;
+    }
+
+    data.get(key_offset..key_offset + 32) == Some(signer.as_ref())
+        && data.get(message_offset..message_offset + message_size) == Some(message)
+}
+
+// price falls from start_price at start_time to floor at end_time
+// exponential decay halves the premium over the floor every half_life seconds,
+// interpolating linearly within each half-life so it only needs integer math
+pub fn compute_dutch_price(
+    start_price: u64,
+    floor: u64,
+    curve: DecayCurve,
+    half_life: u64,
+    start_time: u64,
+    end_time: u64,
+    cur_time: u64
+) -> u64 {
+    if cur_time <= start_time {
+        return start_price;
+    }
+    if cur_time >= end_time {
+        return floor;
+    }
+
+    let premium = start_price - floor;
+    let elapsed = cur_time - start_time;
+
+    let remaining_premium = match curve {
+        DecayCurve::Linear => {
+            let duration = end_time - start_time;
+            premium - ((premium as u128 * elapsed as u128) / duration as u128) as u64
+        }
+        DecayCurve::Exponential => {
+            let halvings = elapsed / half_life;
+            if halvings >= 64 {
+                0
+            } else {
+                let cur = premium >> halvings;
+                let next = cur >> 1;
+                let into_period = elapsed % half_life;
+                cur - (((cur - next) as u128 * into_period as u128) / half_life as u128) as u64
+            }
+        }
+    };
+
+    floor + remaining_premium
+}
+
+// fixed-width encoding so a reserve commitment can only be opened one way
+pub fn compute_reserve_hash(reserve: u64, nonce: u64) -> [u8; 32] {
+    let mut new_hash = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(&reserve.to_le_bytes());
+    hasher.update(&nonce.to_le_bytes());
+    hasher.finalize(&mut new_hash);
+    new_hash
+}
+
+pub const MULTI_UNIT_BID_COMMITMENT_DOMAIN: &[u8] = b"auctionhouse multi-unit sealed bid";
+
+// bound to the auction and bidder like compute_bid_hash_v2, so a copied commitment can't be opened
+pub fn compute_multi_unit_bid_hash(
+    auction: &Pubkey,
+    bidder: &Pubkey,
+    units: u64,
+    price: u64,
+    salt: &[u8; 32]
+) -> [u8; 32] {
+    let mut new_hash = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(MULTI_UNIT_BID_COMMITMENT_DOMAIN);
+    hasher.update(auction.as_ref());
+    hasher.update(bidder.as_ref());
+    hasher.update(&units.to_le_bytes());
+    hasher.update(&price.to_le_bytes());
+    hasher.update(salt);
+    hasher.finalize(&mut new_hash);
+    new_hash
+}
+
+pub const PACKAGE_BID_COMMITMENT_DOMAIN: &[u8] = b"auctionhouse package sealed bid";
+
+// bound to the auction and bidder the same way as compute_multi_unit_bid_hash
+pub fn compute_package_bid_hash(
+    auction: &Pubkey,
+    bidder: &Pubkey,
+    package: u8,
+    bid: u64,
+    salt: &[u8; 32]
+) -> [u8; 32] {
+    let mut new_hash = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(PACKAGE_BID_COMMITMENT_DOMAIN);
+    hasher.update(auction.as_ref());
+    hasher.update(bidder.as_ref());
+    hasher.update(&[package]);
+    hasher.update(&bid.to_le_bytes());
+    hasher.update(salt);
+    hasher.finalize(&mut new_hash);
+    new_hash
+}
+
+// revenue-maximizing set of non-overlapping package bids, where packages are bitmasks of lot indices
+// dp over lot subsets: a subset's lowest lot either goes unsold or is sold in a package bid that fits the subset
+// unrevealed bids have an empty package and never win, equal revenue keeps the earlier choice
+pub fn select_packages(lot_count: usize, packages: &[u8], bids: &[u64]) -> Vec<bool> {
+    let masks = 1usize << lot_count;
+    let mut best = vec![0u64; masks];
+    let mut choice: Vec<Option<usize>> = vec![None; masks];
+    for mask in 1..masks {
+        let lowest = mask & mask.wrapping_neg();
+        best[mask] = best[mask & !lowest];
+        for (i, &package) in packages.iter().enumerate() {
+            let package = package as usize;
+            if package & lowest != 0 && package & !mask == 0 {
+                let revenue = bids[i] + best[mask & !package];
+                if revenue > best[mask] {
+                    best[mask] = revenue;
+                    choice[mask] = Some(i);
+                }
+            }
+        }
+    }
+
+    let mut winners = vec![false; packages.len()];
+    let mut mask = masks - 1;
+    while mask != 0 {
+        match choice[mask] {
+            Some(i) => {
+                winners[i] = true;
+                mask &= !(packages[i] as usize);
+            }
+            None => mask &= mask - 1,
+        }
+    }
+    winners
+}
+
+// fill bids from the highest per-unit price down, the marginal bid may be partially filled
+// equal prices are filled in bid order, so earlier bids win ties
+pub fn allocate_units(supply: u64, bid_units: &[u64], bid_prices: &[u64]) -> Vec<u64> {
+    let mut order: Vec<usize> = (0..bid_units.len()).filter(|&i| bid_units[i] > 0).collect();
+    order.sort_by(|&a, &b| bid_prices[b].cmp(&bid_prices[a]));
+
+    let mut allocations = vec![0u64; bid_units.len()];
+    let mut remaining = supply;
+    for i in order {
+        if remaining == 0 {
+            break;
+        }
+        let filled = std::cmp::min(remaining, bid_units[i]);
+        allocations[i] = filled;
+        remaining -= filled;
+    }
+    allocations
+}
+
+// uniform price paid per unit by every winner
+pub fn compute_clearing_price(
+    rule: ClearingRule,
+    floor: u64,
+    bid_units: &[u64],
+    bid_prices: &[u64],
+    allocations: &[u64]
+) -> u64 {
+    match rule {
+        ClearingRule::LowestWinning => (0..allocations.len())
+            .filter(|&i| allocations[i] > 0)
+            .map(|i| bid_prices[i])
+            .min()
+            .unwrap_or(0),
+        // the unfilled part of a partially filled bid counts as losing
+        // if every bid was filled the floor is the highest losing bid
+        ClearingRule::HighestLosing => (0..allocations.len())
+            .filter(|&i| bid_units[i] > allocations[i])
+            .map(|i| bid_prices[i])
+            .max()
+            .unwrap_or(floor),
+        // see compute_vcg_payments
+        ClearingRule::Vcg => 0,
+    }
+}
+
+// what each bidder owes, the other bids' value without them minus their value with them
+// units nobody else takes are worth the floor to the seller, so every unit costs at least the floor
+pub fn compute_vcg_payments(
+    supply: u64,
+    floor: u64,
+    bid_units: &[u64],
+    bid_prices: &[u64],
+    allocations: &[u64]
+) -> Vec<u64> {
+    let others_value = |allocs: &[u64], skip: usize| -> u128 {
+        let sold: u64 = allocs.iter().sum();
+        let mut value = ((supply - sold) as u128) * (floor as u128);
+        for j in 0..allocs.len() {
+            if j != skip {
+                value += (allocs[j] as u128) * (bid_prices[j] as u128);
+            }
+        }
+        value
+    };
+
+    (0..allocations.len()).map(|i| {
+        if allocations[i] == 0 {
+            return 0;
+        }
+        let mut without_units = bid_units.to_vec();
+        without_units[i] = 0;
+        let without = allocate_units(supply, &without_units, bid_prices);
+        (others_value(&without, i) - others_value(allocations, i)) as u64
+    }).collect()
+}
+
+// hash of a specific slot if it's still in the SlotHashes sysvar
+// read from the raw data since deserializing all 512 entries costs too much compute
+pub fn slot_hash_at(slot_hashes: &AccountInfo, slot: u64) -> Result<Option<[u8; 32]>, ProgramError> {
+    let data = slot_hashes.try_borrow_data()?;
+    if data.len() < 8 {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    // u64 entry count followed by (u64 slot, [u8; 32] hash) entries, newest first
+    let mut len_bytes = [0u8; 8];
+    len_bytes.copy_from_slice(&data[0..8]);
+    let len = u64::from_le_bytes(len_bytes) as usize;
+    for i in 0..len {
+        let offset = 8 + i*40;
+        if data.len() < offset + 40 {
+            break;
+        }
+        let mut slot_bytes = [0u8; 8];
+        slot_bytes.copy_from_slice(&data[offset..offset + 8]);
+        let entry_slot = u64::from_le_bytes(slot_bytes);
+        if entry_slot == slot {
+            let mut hash = [0u8; 32];
+            hash.copy_from_slice(&data[offset + 8..offset + 40]);
+            return Ok(Some(hash));
+        }
+        // entries are sorted newest first
+        if entry_slot < slot {
+            break;
+        }
+    }
+    Ok(None)
+}
+
+// mix a slot hash with an account key so auctions drawn in the same slot get different values
+pub fn random_u64(slot_hash: &[u8; 32], key: &Pubkey) -> u64 {
+    let mut new_hash = [0u8; 32];
+    let mut hasher = Keccak::v256();
+    hasher.update(slot_hash);
+    hasher.update(key.as_ref());
+    hasher.finalize(&mut new_hash);
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&new_hash[0..8]);
+    u64::from_le_bytes(bytes)
+}
+
+// lower keys win between equal bids, open auctions pass the bid time as both times
+// random keys start at 0 and are drawn with random_u64 once the ties are final
+pub fn tie_break_key(tie_break: TieBreak, commit_time: u64, reveal_time: u64) -> u64 {
+    match tie_break {
+        TieBreak::EarliestCommit => commit_time,
+        TieBreak::EarliestReveal => reveal_time,
+        TieBreak::Random => 0,
+    }
+}
+
 pub fn name_seed(name: &str) -> &[u8] {
     let b = name.as_bytes();
     if b.len() > 32 { &b[0..32] } else { b }
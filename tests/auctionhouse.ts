@@ -6,6 +6,7 @@ import * as web3 from '@solana/web3.js';
 import { Auctionhouse } from '../target/types/auctionhouse';
 import * as assert from "assert";
 import { keccak_256 } from 'js-sha3';
+import * as nacl from 'tweetnacl';
 
 function lamports(sol: number): number{
   return sol*anchor.web3.LAMPORTS_PER_SOL;
@@ -60,6 +61,21 @@ async function deriveOpenAuction(program,
   return [auctionAddress, bump, auctionAta];
 }
 
+// every optional open auction feature is off unless a test overrides it
+function openAuctionOptions(overrides = {}) {
+  return {
+    softClose: null,
+    buyNow: null,
+    reserve: null,
+    proxyBidding: false,
+    candle: null,
+    bundleAmounts: [],
+    units: new anchor.BN(1),
+    tieBreak: { earliestCommit: {} },
+    ...overrides,
+  };
+}
+
 async function deriveSealedAuction(program,
   ownerAddress: web3.PublicKey,
   mintAddress: web3.PublicKey,
@@ -73,6 +89,207 @@ async function deriveSealedAuction(program,
   return [auctionAddress, bump, auctionAta];
 }
 
+// every optional sealed auction feature is off unless a test overrides it
+function sealedAuctionOptions(overrides = {}) {
+  return {
+    bundleAmounts: [],
+    commitmentVersion: 2,
+    penalty: null,
+    deposits: null,
+    auctioneer: null,
+    tieBreak: { earliestReveal: {} },
+    ...overrides,
+  };
+}
+
+function bidHashV2(auctionAddress: web3.PublicKey,
+  bidderAddress: web3.PublicKey,
+  bid: number,
+  salt: Uint8Array
+): Uint8Array {
+  let hash = keccak_256.create();
+  hash.update(Buffer.from("auctionhouse sealed bid v2"));
+  hash.update(auctionAddress.toBytes());
+  hash.update(bidderAddress.toBytes());
+  hash.update(new anchor.BN(bid).toArrayLike(Buffer, 'le', 8));
+  hash.update(salt);
+  return Uint8Array.from(Buffer.from(hash.hex(), 'hex'));
+}
+
+// what a bidder signs so a relayer can reveal for them
+function revealMessage(auctionAddress: web3.PublicKey, bid: number, salt: Uint8Array): Buffer {
+  return Buffer.concat([
+    Buffer.from("auctionhouse sealed reveal"),
+    auctionAddress.toBuffer(),
+    new anchor.BN(bid).toArrayLike(Buffer, 'le', 8),
+    Buffer.from(salt),
+  ]);
+}
+
+// ephemeral x25519 key, nonce, then the boxed bid and salt
+function encryptBid(auctioneerKey: Uint8Array, bid: number, salt: Uint8Array): Buffer {
+  let ephemeral = nacl.box.keyPair();
+  let nonce = nacl.randomBytes(nacl.box.nonceLength);
+  let message = Buffer.concat([new anchor.BN(bid).toArrayLike(Buffer, 'le', 8), Buffer.from(salt)]);
+  let boxed = nacl.box(message, nonce, auctioneerKey, ephemeral.secretKey);
+  return Buffer.concat([Buffer.from(ephemeral.publicKey), Buffer.from(nonce), Buffer.from(boxed)]);
+}
+
+function decryptBid(auctioneerSecret: Uint8Array, ciphertext: Buffer): [bid: anchor.BN, salt: number[]] {
+  let ephemeralKey = ciphertext.slice(0, 32);
+  let nonce = ciphertext.slice(32, 32 + nacl.box.nonceLength);
+  let boxed = ciphertext.slice(32 + nacl.box.nonceLength);
+  let message = Buffer.from(nacl.box.open(boxed, nonce, ephemeralKey, auctioneerSecret));
+  return [new anchor.BN(message.slice(0, 8), 'le'), Array.from(message.slice(8, 40))];
+}
+
+async function deriveDutchAuction(program,
+  ownerAddress: web3.PublicKey,
+  mintAddress: web3.PublicKey,
+  auctionTitle: string
+): Promise<[auctionAddress: web3.PublicKey, bump: number, auctionAta: web3.PublicKey]> {
+  const [auctionAddress, bump] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from("dutch auction"), ownerAddress.toBytes(), Buffer.from(auctionTitle.slice(0, 32))],
+    program.programId
+  )
+  let auctionAta = await serumAta.getAssociatedTokenAddress(auctionAddress, mintAddress);
+  return [auctionAddress, bump, auctionAta];
+}
+
+function reserveHash(reserve: number, nonce: number): Uint8Array {
+  let hash = keccak_256.create();
+  hash.update(new anchor.BN(reserve).toArrayLike(Buffer, 'le', 8));
+  hash.update(new anchor.BN(nonce).toArrayLike(Buffer, 'le', 8));
+  return Uint8Array.from(Buffer.from(hash.hex(), 'hex'));
+}
+
+async function deriveMultiUnitSealedAuction(program,
+  ownerAddress: web3.PublicKey,
+  mintAddress: web3.PublicKey,
+  auctionTitle: string
+): Promise<[auctionAddress: web3.PublicKey, bump: number, auctionAta: web3.PublicKey]> {
+  const [auctionAddress, bump] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from("multi unit sealed auction"), ownerAddress.toBytes(), Buffer.from(auctionTitle.slice(0, 32))],
+    program.programId
+  )
+  let auctionAta = await serumAta.getAssociatedTokenAddress(auctionAddress, mintAddress);
+  return [auctionAddress, bump, auctionAta];
+}
+
+function multiUnitBidHash(auctionAddress: web3.PublicKey,
+  bidderAddress: web3.PublicKey,
+  units: number,
+  price: number,
+  salt: Uint8Array
+): Uint8Array {
+  let hash = keccak_256.create();
+  hash.update(Buffer.from("auctionhouse multi-unit sealed bid"));
+  hash.update(auctionAddress.toBytes());
+  hash.update(bidderAddress.toBytes());
+  hash.update(new anchor.BN(units).toArrayLike(Buffer, 'le', 8));
+  hash.update(new anchor.BN(price).toArrayLike(Buffer, 'le', 8));
+  hash.update(salt);
+  return Uint8Array.from(Buffer.from(hash.hex(), 'hex'));
+}
+
+async function deriveReverseAuction(program,
+  ownerAddress: web3.PublicKey,
+  mintAddress: web3.PublicKey,
+  auctionTitle: string
+): Promise<[auctionAddress: web3.PublicKey, bump: number, auctionAta: web3.PublicKey]> {
+  const [auctionAddress, bump] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from("reverse auction"), ownerAddress.toBytes(), Buffer.from(auctionTitle.slice(0, 32))],
+    program.programId
+  )
+  let auctionAta = await serumAta.getAssociatedTokenAddress(auctionAddress, mintAddress);
+  return [auctionAddress, bump, auctionAta];
+}
+
+async function deriveClockAuction(program,
+  ownerAddress: web3.PublicKey,
+  mintAddress: web3.PublicKey,
+  auctionTitle: string
+): Promise<[auctionAddress: web3.PublicKey, bump: number, auctionAta: web3.PublicKey]> {
+  const [auctionAddress, bump] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from("clock auction"), ownerAddress.toBytes(), Buffer.from(auctionTitle.slice(0, 32))],
+    program.programId
+  )
+  let auctionAta = await serumAta.getAssociatedTokenAddress(auctionAddress, mintAddress);
+  return [auctionAddress, bump, auctionAta];
+}
+
+async function deriveRaffle(program,
+  ownerAddress: web3.PublicKey,
+  mintAddress: web3.PublicKey,
+  raffleTitle: string
+): Promise<[raffleAddress: web3.PublicKey, bump: number, raffleAta: web3.PublicKey]> {
+  const [raffleAddress, bump] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from("raffle"), ownerAddress.toBytes(), Buffer.from(raffleTitle.slice(0, 32))],
+    program.programId
+  )
+  let raffleAta = await serumAta.getAssociatedTokenAddress(raffleAddress, mintAddress);
+  return [raffleAddress, bump, raffleAta];
+}
+
+async function derivePackageSealedAuction(program,
+  ownerAddress: web3.PublicKey,
+  auctionTitle: string
+): Promise<[auctionAddress: web3.PublicKey, bump: number]> {
+  const [auctionAddress, bump] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from("package sealed auction"), ownerAddress.toBytes(), Buffer.from(auctionTitle.slice(0, 32))],
+    program.programId
+  )
+  return [auctionAddress, bump];
+}
+
+function packageBidHash(auctionAddress: web3.PublicKey,
+  bidderAddress: web3.PublicKey,
+  packageMask: number,
+  bid: number,
+  salt: Uint8Array
+): Uint8Array {
+  let hash = keccak_256.create();
+  hash.update(Buffer.from("auctionhouse package sealed bid"));
+  hash.update(auctionAddress.toBytes());
+  hash.update(bidderAddress.toBytes());
+  hash.update([packageMask]);
+  hash.update(new anchor.BN(bid).toArrayLike(Buffer, 'le', 8));
+  hash.update(salt);
+  return Uint8Array.from(Buffer.from(hash.hex(), 'hex'));
+}
+
+async function deriveCatalogAuction(program,
+  ownerAddress: web3.PublicKey,
+  auctionTitle: string
+): Promise<[auctionAddress: web3.PublicKey, bump: number]> {
+  const [auctionAddress, bump] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from("catalog auction"), ownerAddress.toBytes(), Buffer.from(auctionTitle.slice(0, 32))],
+    program.programId
+  )
+  return [auctionAddress, bump];
+}
+
+async function deriveSeriesAuction(program,
+  ownerAddress: web3.PublicKey,
+  auctionTitle: string
+): Promise<[auctionAddress: web3.PublicKey, bump: number]> {
+  const [auctionAddress, bump] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from("series auction"), ownerAddress.toBytes(), Buffer.from(auctionTitle.slice(0, 32))],
+    program.programId
+  )
+  return [auctionAddress, bump];
+}
+
+async function deriveSeriesConfig(program,
+  auctionAddress: web3.PublicKey
+): Promise<[configAddress: web3.PublicKey, configBump: number]> {
+  const [configAddress, configBump] = await anchor.web3.PublicKey.findProgramAddress(
+    [Buffer.from("series config"), auctionAddress.toBytes()],
+    program.programId
+  )
+  return [configAddress, configBump];
+}
+
 // Configure the client to use the local cluster.
 anchor.setProvider(anchor.Provider.env());
 
@@ -155,7 +372,8 @@ describe('open auction', () => {
                                     new anchor.BN(startTime),
                                     new anchor.BN(endTime),
                                     new anchor.BN(biddercap),
-                                    new anchor.BN(amount), {
+                                    new anchor.BN(amount),
+                                    openAuctionOptions(), {
         accounts: {
           auction: auctionAddress,
           auctionAta: auctionAta,
@@ -390,7 +608,8 @@ describe('sealed auction', () => {
                                     new anchor.BN(endTime),
                                     new anchor.BN(revealTime),
                                     new anchor.BN(biddercap),
-                                    new anchor.BN(amount), {
+                                    new anchor.BN(amount),
+                                    sealedAuctionOptions({ commitmentVersion: 1 }), {
         accounts: {
           auction: auctionAddress,
           auctionAta: auctionAta,
@@ -430,7 +649,7 @@ describe('sealed auction', () => {
     hash.update(losingBidNonce.toString());
     losingBidHash = Uint8Array.from(Buffer.from(hash.hex(), 'hex'));
 
-    await program.rpc.makeSealedBid(losingBidHash, new anchor.BN(fakeLosingBid), {
+    await program.rpc.makeSealedBid(losingBidHash, new anchor.BN(fakeLosingBid), Buffer.from([]), {
       accounts: {
         auction: auctionAddress,
         bidder: loser.publicKey,
@@ -461,7 +680,7 @@ describe('sealed auction', () => {
     hash.update(winningBidNonce.toString());
     winningBidHash = Uint8Array.from(Buffer.from(hash.hex(), 'hex'));
 
-    await program.rpc.makeSealedBid(winningBidHash, new anchor.BN(fakeWinningBid), {
+    await program.rpc.makeSealedBid(winningBidHash, new anchor.BN(fakeWinningBid), Buffer.from([]), {
       accounts: {
         auction: auctionAddress,
         bidder: buyer.publicKey,
@@ -579,4 +798,5172 @@ describe('sealed auction', () => {
     const auctionAccounts = await program.account.sealedAuction.all();
     assert.equal(auctionAccounts.length, 1);
   });
+});
+
+describe('dutch auction', () => {
+
+  let seller;
+  let buyer;
+  let mintOwner;
+  let decimals;
+  let mintAmount;
+  let mint;
+  let sellerAta;
+  let buyerAtaAddress;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let startPrice;
+  let floor;
+  let initialAuctionBalance;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    buyer = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, buyer.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    decimals = 9;
+    mintAmount = Math.pow(10, decimals);
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      decimals,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    // dont create the ata now so that the contract will do it in buy_dutch
+    buyerAtaAddress = await serumAta.getAssociatedTokenAddress(buyer.publicKey, mint.publicKey);
+
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    let auctionTitle = "spl dutch test";
+    startPrice = lamports(2);
+    floor = lamports(1);
+    let halfLife = 30;
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 60;
+    let amount = mintAmount;
+
+    [auctionAddress, bump, auctionAta] = await deriveDutchAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createDutchAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(startPrice),
+                                    new anchor.BN(floor),
+                                    { exponential: {} },
+                                    new anchor.BN(halfLife),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(amount), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+
+    amt = await getTokenBalance(program, auctionAta);
+    assert.equal(amt.amount, mintAmount);
+  });
+
+  it('buy item', async () => {
+    let sellerBalance = await getLamportBalance(program, seller.publicKey);
+
+    await program.rpc.buyDutch({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        owner: seller.publicKey,
+        buyer: buyer.publicKey,
+        buyerAta: buyerAtaAddress,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [buyer]
+    });
+
+    auctionAccount = await program.account.dutchAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.sold, true);
+    assert.equal(auctionAccount.buyer.toBase58(), buyer.publicKey.toBase58());
+    // two half-lives have passed so the premium over the floor has roughly quartered
+    assert.ok(auctionAccount.salePrice.toNumber() > floor);
+    assert.ok(auctionAccount.salePrice.toNumber() < startPrice);
+
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - sellerBalance, auctionAccount.salePrice.toNumber());
+
+    amt = await getTokenBalance(program, buyerAtaAddress);
+    assert.equal(amt.amount, mintAmount);
+
+    // make sure auction hasn't kept any sol
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt, initialAuctionBalance);
+  });
+
+  it('fetch auction', async () => {
+    const auctionAccounts = await program.account.dutchAuction.all();
+    assert.equal(auctionAccounts.length, 1);
+  });
+
+});
+
+describe('open auction soft close', () => {
+
+  let seller;
+  let bidder;
+  let mintOwner;
+  let mintAmount;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let endTime;
+  let extensionWindow;
+  let extensionLength;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    bidder = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, bidder.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    mintAmount = 1;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    let auctionTitle = "soft close test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    endTime = Math.floor(Date.now() / 1000) + 5;
+    extensionWindow = 60;
+    extensionLength = 30;
+    let maxExtensions = 1;
+
+    [auctionAddress, bump, auctionAta] = await deriveOpenAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createOpenAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(lamports(0.05)),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(mintAmount),
+                                    openAuctionOptions({
+                                      softClose: { window: new anchor.BN(extensionWindow), length: new anchor.BN(extensionLength), maxExtensions: new anchor.BN(maxExtensions) },
+                                    }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+  });
+
+  it('late bid extends end time', async () => {
+    await program.rpc.makeOpenBid(new anchor.BN(lamports(1)), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder]
+    });
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.endTime.toNumber(), endTime + extensionLength);
+    assert.equal(auctionAccount.extensions.toNumber(), 1);
+  });
+
+  it('extension cap is respected', async () => {
+    await program.rpc.makeOpenBid(new anchor.BN(lamports(1)), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder]
+    });
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.endTime.toNumber(), endTime + extensionLength);
+    assert.equal(auctionAccount.extensions.toNumber(), 1);
+  });
+
+  delay(auctionEndDelay, "delay past the original end time");
+
+  it('cancel auction before the extended end time', async () => {
+    await program.rpc.cancelOpenAuction({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.cancelled, true);
+  });
+
+});
+
+describe('open auction buy now', () => {
+
+  let seller;
+  let bidder;
+  let buyer;
+  let mintOwner;
+  let mintAmount;
+  let mint;
+  let sellerAta;
+  let buyerAtaAddress;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let bid;
+  let buyNowPrice;
+  let initialAuctionBalance;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    bidder = anchor.web3.Keypair.generate();
+    buyer = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, bidder.publicKey, lamports(5));
+    await airdrop(program, buyer.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    mintAmount = 1;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    buyerAtaAddress = await serumAta.getAssociatedTokenAddress(buyer.publicKey, mint.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    let auctionTitle = "buy now test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 60;
+    buyNowPrice = lamports(2);
+    // buy-now closes once bidding reaches half of the buy-now price
+    let buyNowCutoffBps = 5000;
+
+    [auctionAddress, bump, auctionAta] = await deriveOpenAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createOpenAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(lamports(0.05)),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(mintAmount),
+                                    openAuctionOptions({
+                                      buyNow: { price: new anchor.BN(buyNowPrice), cutoffBps: new anchor.BN(buyNowCutoffBps) },
+                                    }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+  });
+
+  it('make bid under the buy-now cutoff', async () => {
+    bid = lamports(0.5);
+
+    await program.rpc.makeOpenBid(new anchor.BN(bid), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder]
+    });
+  });
+
+  it('buy now', async () => {
+    await program.rpc.buyNowOpen({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        buyer: buyer.publicKey,
+        buyerAta: buyerAtaAddress,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [buyer]
+    });
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.highestBidder.toBase58(), buyer.publicKey.toBase58());
+    assert.equal(auctionAccount.highestBid, buyNowPrice);
+    assert.equal(auctionAccount.itemWithdrawn, true);
+
+    amt = await getTokenBalance(program, buyerAtaAddress);
+    assert.equal(amt.amount, mintAmount);
+  });
+
+  it('reclaim outbid bid', async () => {
+    let initialBalance = await getLamportBalance(program, bidder.publicKey);
+
+    await program.rpc.reclaimOpenBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder]
+    });
+
+    amt = await getLamportBalance(program, bidder.publicKey);
+    assert.equal(amt - initialBalance, bid);
+  });
+
+  it('withdraw buy-now payment', async () => {
+    let initialBalance = await getLamportBalance(program, seller.publicKey);
+
+    await program.rpc.withdrawWinningBidOpen({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - initialBalance, buyNowPrice);
+
+    // make sure auction hasn't kept any sol
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt, initialAuctionBalance);
+  });
+
+});
+
+describe('open auction hidden reserve', () => {
+
+  let seller;
+  let bidder;
+  let mintOwner;
+  let mintAmount;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let bid;
+  let reserve;
+  let reserveNonce;
+  let initialAuctionBalance;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    bidder = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, bidder.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    mintAmount = 1;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    let auctionTitle = "hidden reserve test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    reserve = lamports(3);
+    reserveNonce = randomInt(100000, 1000000);
+
+    [auctionAddress, bump, auctionAta] = await deriveOpenAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createOpenAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(lamports(0.05)),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(mintAmount),
+                                    openAuctionOptions({
+                                      reserve: { hash: reserveHash(reserve, reserveNonce), revealWindow: new anchor.BN(60) },
+                                    }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+  });
+
+  it('make bid under the reserve', async () => {
+    bid = lamports(1);
+
+    await program.rpc.makeOpenBid(new anchor.BN(bid), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder]
+    });
+  });
+
+  delay(auctionEndDelay, "delay for auction period to end");
+
+  it('reveal reserve', async () => {
+    await program.rpc.revealReserveOpen(new anchor.BN(reserve), new anchor.BN(reserveNonce), {
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.reserveRevealed, true);
+    assert.equal(auctionAccount.reservePrice, reserve);
+  });
+
+  it('highest bidder reclaims bid under the reserve', async () => {
+    let initialBalance = await getLamportBalance(program, bidder.publicKey);
+
+    await program.rpc.reclaimOpenBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder]
+    });
+
+    amt = await getLamportBalance(program, bidder.publicKey);
+    assert.equal(amt - initialBalance, bid);
+
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt, initialAuctionBalance);
+  });
+
+  it('seller reclaims item', async () => {
+    await program.rpc.reclaimItemOpen({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        owner: seller.publicKey,
+        ownerAta: sellerAta.address,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [seller]
+    });
+
+    amt = await getTokenBalance(program, sellerAta.address);
+    assert.equal(amt.amount, mintAmount);
+  });
+
+});
+
+describe('open auction proxy bidding', () => {
+
+  let seller;
+  let loser;
+  let buyer;
+  let mintOwner;
+  let mintAmount;
+  let mint;
+  let sellerAta;
+  let buyerAtaAddress;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let increment;
+  let losingMax;
+  let winningMax;
+  let initialAuctionBalance;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    loser = anchor.web3.Keypair.generate();
+    buyer = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, loser.publicKey, lamports(5));
+    await airdrop(program, buyer.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    mintAmount = 1;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    buyerAtaAddress = await serumAta.getAssociatedTokenAddress(buyer.publicKey, mint.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    let auctionTitle = "proxy bidding test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    increment = lamports(0.05);
+
+    [auctionAddress, bump, auctionAta] = await deriveOpenAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createOpenAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(increment),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(mintAmount),
+                                    openAuctionOptions({ proxyBidding: true }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+  });
+
+  it('make winning proxy bid', async () => {
+    winningMax = lamports(3);
+
+    await program.rpc.makeOpenBid(new anchor.BN(winningMax), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    });
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.highestBidder.toBase58(), buyer.publicKey.toBase58());
+    assert.equal(auctionAccount.highestBid, lamports(0.1) + increment);
+  });
+
+  it('losing proxy bid is outbid automatically', async () => {
+    losingMax = lamports(1.5);
+
+    await program.rpc.makeOpenBid(new anchor.BN(losingMax), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: loser.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [loser]
+    });
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.highestBidder.toBase58(), buyer.publicKey.toBase58());
+    assert.equal(auctionAccount.highestBid, losingMax + increment);
+  });
+
+  it('reclaim losing proxy bid', async () => {
+    let initialBalance = await getLamportBalance(program, loser.publicKey);
+
+    await program.rpc.reclaimOpenBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: loser.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [loser]
+    });
+
+    amt = await getLamportBalance(program, loser.publicKey);
+    assert.equal(amt - initialBalance, losingMax);
+  });
+
+  delay(auctionEndDelay, "delay for auction period to end");
+
+  it('withdraw winning bid', async () => {
+    let initialBalance = await getLamportBalance(program, seller.publicKey);
+
+    await program.rpc.withdrawWinningBidOpen({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - initialBalance, losingMax + increment);
+  });
+
+  it('withdraw item and unused proxy escrow', async () => {
+    let initialBalance = await getLamportBalance(program, buyer.publicKey);
+
+    await program.rpc.withdrawItemOpen({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        highestBidder: buyer.publicKey,
+        highestBidderAta: buyerAtaAddress,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [buyer]
+    });
+
+    amt = await getTokenBalance(program, buyerAtaAddress);
+    assert.equal(amt.amount, mintAmount);
+
+    amt = await getLamportBalance(program, buyer.publicKey);
+    // buyer refunded unused maximum minus cost to create ATA
+    assert.ok(amt - initialBalance > (winningMax - losingMax - increment - 10000000));
+
+    // make sure auction hasn't kept any sol
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt, initialAuctionBalance);
+  });
+
+});
+
+describe('multi-unit sealed auction', () => {
+
+  let seller;
+  let smallBuyer;
+  let bigBuyer;
+  let mintOwner;
+  let mintAmount;
+  let units;
+  let mint;
+  let sellerAta;
+  let smallBuyerAta;
+  let bigBuyerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let smallBid;
+  let bigBid;
+  let initialAuctionBalance;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    smallBuyer = anchor.web3.Keypair.generate();
+    bigBuyer = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(10));
+    await airdrop(program, smallBuyer.publicKey, lamports(10));
+    await airdrop(program, bigBuyer.publicKey, lamports(10));
+    await airdrop(program, mintOwner.publicKey, lamports(10));
+
+    units = 2;
+    mintAmount = 10;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    smallBuyerAta = await serumAta.getAssociatedTokenAddress(smallBuyer.publicKey, mint.publicKey);
+    bigBuyerAta = await serumAta.getAssociatedTokenAddress(bigBuyer.publicKey, mint.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    let auctionTitle = "multi unit test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let revealTime = Math.floor(Date.now() / 1000) + 10;
+
+    [auctionAddress, bump, auctionAta] = await deriveMultiUnitSealedAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createMultiUnitSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    { lowestWinning: {} },
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(mintAmount),
+                                    new anchor.BN(units), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+  });
+
+  it('make sealed bids', async () => {
+    // one unit at 2 SOL and two units at 1 SOL each
+    smallBid = { units: 1, price: lamports(2), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2.5) };
+    bigBid = { units: 2, price: lamports(1), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2.5) };
+
+    for (const [bid, bidder] of [[smallBid, smallBuyer], [bigBid, bigBuyer]]) {
+      await program.rpc.makeMultiUnitSealedBid(multiUnitBidHash(auctionAddress, bidder.publicKey, bid.units, bid.price, bid.salt),
+                                               new anchor.BN(bid.deposit), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt - initialAuctionBalance, smallBid.deposit + bigBid.deposit);
+  });
+
+  delay(auctionEndDelay, "delay for reveal period to start");
+
+  it('reveal sealed bids', async () => {
+    for (const [bid, bidder] of [[smallBid, smallBuyer], [bigBid, bigBuyer]]) {
+      await program.rpc.revealMultiUnitSealedBid(new anchor.BN(bid.units),
+                                                 new anchor.BN(bid.price),
+                                                 Array.from(bid.salt), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+  });
+
+  delay(revealPeriodEndDelay, "delay for reveal period to end");
+
+  it('settle auction', async () => {
+    await program.rpc.settleMultiUnitSealedAuction({
+      accounts: {
+        auction: auctionAddress,
+      },
+    });
+
+    auctionAccount = await program.account.multiUnitSealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.settled, true);
+    assert.equal(auctionAccount.unitsSold.toNumber(), units);
+    // the big bid is partially filled and sets the uniform price
+    assert.equal(auctionAccount.clearingPrice, bigBid.price);
+    assert.equal(auctionAccount.allocations[0].toNumber(), 1);
+    assert.equal(auctionAccount.allocations[1].toNumber(), 1);
+  });
+
+  it('withdraw units and refunds', async () => {
+    for (const [bid, bidder, ata] of [[smallBid, smallBuyer, smallBuyerAta], [bigBid, bigBuyer, bigBuyerAta]]) {
+      let initialBalance = await getLamportBalance(program, bidder.publicKey);
+
+      await program.rpc.withdrawMultiUnitSealed({
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          bidder: bidder.publicKey,
+          bidderAta: ata,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [bidder]
+      });
+
+      amt = await getTokenBalance(program, ata);
+      assert.equal(amt.amount, mintAmount / units);
+
+      amt = await getLamportBalance(program, bidder.publicKey);
+      // refunded deposit minus one unit at the clearing price minus cost to create ATA
+      assert.ok(amt - initialBalance > (bid.deposit - bigBid.price - 10000000));
+    }
+  });
+
+  it('withdraw proceeds', async () => {
+    let initialBalance = await getLamportBalance(program, seller.publicKey);
+
+    await program.rpc.withdrawProceedsMultiUnitSealed({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - initialBalance, units * bigBid.price);
+
+    // make sure auction hasn't kept any sol
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt, initialAuctionBalance);
+  });
+
+});
+
+describe('open auction candle close', () => {
+
+  let seller;
+  let earlyBidder;
+  let lateBidder;
+  let mintOwner;
+  let mintAmount;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let earlyBid;
+  let lateBid;
+  let candleWindow;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    earlyBidder = anchor.web3.Keypair.generate();
+    lateBidder = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, earlyBidder.publicKey, lamports(5));
+    await airdrop(program, lateBidder.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    mintAmount = 1;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    let auctionTitle = "candle test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    // the whole remaining auction is inside the candle window
+    candleWindow = 30;
+
+    [auctionAddress, bump, auctionAta] = await deriveOpenAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createOpenAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(lamports(0.05)),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(mintAmount),
+                                    openAuctionOptions({
+                                      candle: { window: new anchor.BN(candleWindow), logCap: new anchor.BN(4) },
+                                    }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+  });
+
+  it('bids inside the candle window are logged', async () => {
+    earlyBid = lamports(0.5);
+    lateBid = lamports(1);
+
+    for (const [bid, bidder] of [[earlyBid, earlyBidder], [lateBid, lateBidder]]) {
+      await program.rpc.makeOpenBid(new anchor.BN(bid), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    let last = auctionAccount.candleLog[auctionAccount.candleLog.length - 1];
+    assert.equal(last.bidder.toBase58(), lateBidder.publicKey.toBase58());
+    assert.equal(last.bid.toNumber(), lateBid);
+  });
+
+  it('a raise by the leader only updates their entry', async () => {
+    let logLength = auctionAccount.candleLog.length;
+
+    await program.rpc.makeOpenBid(new anchor.BN(lamports(0.25)), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: lateBidder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [lateBidder]
+    });
+    lateBid += lamports(0.25);
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.candleLog.length, logLength);
+    assert.equal(auctionAccount.candleLog[logLength - 1].bid.toNumber(), lateBid);
+  });
+
+  it('lead changes past the log cap are still accepted', async () => {
+    // 30 seconds in 4 buckets, so every lead change after the first few lands in a filled bucket
+    for (const [amount, bidder] of [[1, earlyBidder], [0.5, lateBidder], [0.5, earlyBidder], [0.5, lateBidder]]) {
+      await program.rpc.makeOpenBid(new anchor.BN(lamports(amount)), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+    earlyBid = lamports(2);
+    lateBid = lamports(2.25);
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.ok(auctionAccount.candleLog.length <= 4);
+    let last = auctionAccount.candleLog[auctionAccount.candleLog.length - 1];
+    assert.equal(last.bidder.toBase58(), lateBidder.publicKey.toBase58());
+    assert.equal(last.bid.toNumber(), lateBid);
+  });
+
+  it('logged bids are locked until the close is drawn', async () => {
+    for (const bidder of [earlyBidder, lateBidder]) {
+      if (!auctionAccount.candleLog.some(entry => entry.bidder.equals(bidder.publicKey))) {
+        continue;
+      }
+
+      await assert.rejects(program.rpc.reclaimOpenBid({
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      }));
+    }
+  });
+
+  delay(auctionEndDelay, "delay for auction period to end");
+
+  it('commit candle draw slot', async () => {
+    await program.rpc.commitCandleDrawOpen({
+      accounts: {
+        auction: auctionAddress,
+        slotHashes: web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      },
+    });
+
+    let auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.ok(auctionAccount.candleDrawSlot.toNumber() > 0);
+  });
+
+  delay(auctionEndDelay, "delay for the draw slot to pass");
+
+  it('draw candle close', async () => {
+    await program.rpc.resolveCandleOpen({
+      accounts: {
+        auction: auctionAddress,
+        slotHashes: web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      },
+    });
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.candleResolved, true);
+
+    // the winner is the last logged lead at or before the drawn cutoff
+    let cutoff = auctionAccount.candleCutoff.toNumber();
+    let expected = auctionAccount.candleLog.filter(entry => entry.time.toNumber() <= cutoff).pop();
+    if (expected) {
+      assert.equal(auctionAccount.highestBidder.toBase58(), expected.bidder.toBase58());
+      assert.equal(auctionAccount.highestBid.toNumber(), expected.bid.toNumber());
+    } else {
+      assert.equal(auctionAccount.highestBid.toNumber(), 0);
+    }
+  });
+
+});
+
+describe('reverse auction', () => {
+
+  let buyer;
+  let supplier1;
+  let supplier2;
+  let mintOwner;
+  let mintAmount;
+  let mint;
+  let buyerAtaAddress;
+  let supplier1Ata;
+  let supplier2Ata;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let budget;
+  let losingOffer;
+  let winningOffer;
+  let initialAuctionBalance;
+
+  let amt;
+
+  it('init auction', async () => {
+    buyer = anchor.web3.Keypair.generate();
+    supplier1 = anchor.web3.Keypair.generate();
+    supplier2 = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, buyer.publicKey, lamports(5));
+    await airdrop(program, supplier1.publicKey, lamports(5));
+    await airdrop(program, supplier2.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    mintAmount = 10;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    supplier1Ata = await mint.getOrCreateAssociatedAccountInfo(supplier1.publicKey);
+    supplier2Ata = await mint.getOrCreateAssociatedAccountInfo(supplier2.publicKey);
+    // dont create the ata now so that the contract will do it in withdraw_item_reverse
+    buyerAtaAddress = await serumAta.getAssociatedTokenAddress(buyer.publicKey, mint.publicKey);
+
+    await mint.mintTo(supplier1Ata.address, mintOwner.publicKey, [], mintAmount);
+    await mint.mintTo(supplier2Ata.address, mintOwner.publicKey, [], mintAmount);
+
+    let auctionTitle = "reverse test";
+    budget = lamports(2);
+    let decrement = lamports(0.1);
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let supplierCap = 2;
+
+    [auctionAddress, bump, auctionAta] = await deriveReverseAuction(program, buyer.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createReverseAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(budget),
+                                    new anchor.BN(decrement),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(supplierCap),
+                                    new anchor.BN(mintAmount), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: buyer.publicKey,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [buyer],
+    });
+
+    // includes the escrowed budget
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+
+    auctionAccount = await program.account.reverseAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.budget.toNumber(), budget);
+  });
+
+  it('offer into a foreign token account fails', async () => {
+    // the supplier's own ata would keep their tokens while still recording the offer
+    await assert.rejects(program.rpc.makeReverseOffer(new anchor.BN(lamports(1.5)), {
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: supplier1Ata.address,
+        supplier: supplier1.publicKey,
+        supplierAta: supplier1Ata.address,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+      },
+      signers: [supplier1]
+    }));
+
+    auctionAccount = await program.account.reverseAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.suppliers.length, 0);
+  });
+
+  it('make losing offer', async () => {
+    losingOffer = lamports(1.5);
+
+    await program.rpc.makeReverseOffer(new anchor.BN(losingOffer), {
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        supplier: supplier1.publicKey,
+        supplierAta: supplier1Ata.address,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+      },
+      signers: [supplier1]
+    });
+
+    amt = await getTokenBalance(program, auctionAta);
+    assert.equal(amt.amount, mintAmount);
+  });
+
+  it('make winning offer', async () => {
+    winningOffer = lamports(1);
+
+    await program.rpc.makeReverseOffer(new anchor.BN(winningOffer), {
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        supplier: supplier2.publicKey,
+        supplierAta: supplier2Ata.address,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+      },
+      signers: [supplier2]
+    });
+
+    auctionAccount = await program.account.reverseAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.lowestSupplier.toBase58(), supplier2.publicKey.toBase58());
+    assert.equal(auctionAccount.lowestOffer.toNumber(), winningOffer);
+
+    amt = await getTokenBalance(program, auctionAta);
+    assert.equal(amt.amount, 2*mintAmount);
+  });
+
+  it('reclaim losing offer', async () => {
+    await program.rpc.reclaimReverseOffer({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        supplier: supplier1.publicKey,
+        supplierAta: supplier1Ata.address,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [supplier1]
+    });
+
+    amt = await getTokenBalance(program, supplier1Ata.address);
+    assert.equal(amt.amount, mintAmount);
+  });
+
+  delay(auctionEndDelay, "delay for auction period to end");
+
+  it('withdraw item', async () => {
+    await program.rpc.withdrawItemReverse({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        owner: buyer.publicKey,
+        ownerAta: buyerAtaAddress,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [buyer]
+    });
+
+    amt = await getTokenBalance(program, buyerAtaAddress);
+    assert.equal(amt.amount, mintAmount);
+  });
+
+  it('withdraw winning payment', async () => {
+    let supplierBalance = await getLamportBalance(program, supplier2.publicKey);
+
+    await program.rpc.withdrawPaymentReverse({
+      accounts: {
+        auction: auctionAddress,
+        lowestSupplier: supplier2.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [supplier2]
+    });
+
+    amt = await getLamportBalance(program, supplier2.publicKey);
+    assert.equal(amt - supplierBalance, winningOffer);
+  });
+
+  it('reclaim unspent budget', async () => {
+    let buyerBalance = await getLamportBalance(program, buyer.publicKey);
+
+    await program.rpc.reclaimBudgetReverse({
+      accounts: {
+        auction: auctionAddress,
+        owner: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    });
+
+    amt = await getLamportBalance(program, buyer.publicKey);
+    assert.equal(amt - buyerBalance, budget - winningOffer);
+
+    // only rent is left behind
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt, initialAuctionBalance - budget);
+  });
+
+});
+
+describe('clock auction', () => {
+
+  let seller;
+  let bidder1;
+  let bidder2;
+  let mintOwner;
+  let mintAmount;
+  let mint;
+  let sellerAta;
+  let bidder2AtaAddress;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let deposit1;
+  let deposit2;
+  let initialAuctionBalance;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    bidder1 = anchor.web3.Keypair.generate();
+    bidder2 = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, bidder1.publicKey, lamports(5));
+    await airdrop(program, bidder2.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    mintAmount = 1;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    // dont create the ata now so that the contract will do it in withdraw_item_clock
+    bidder2AtaAddress = await serumAta.getAssociatedTokenAddress(bidder2.publicKey, mint.publicKey);
+
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    let auctionTitle = "clock test";
+    let floor = lamports(0.5);
+    // slow enough that neither deposit runs out during the test
+    let increment = lamports(0.01);
+    let stepLength = 1;
+    let startTime = Math.floor(Date.now() / 1000) + 5;
+    let bidderCap = 2;
+
+    [auctionAddress, bump, auctionAta] = await deriveClockAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createClockAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(floor),
+                                    new anchor.BN(increment),
+                                    new anchor.BN(stepLength),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(bidderCap),
+                                    new anchor.BN(mintAmount), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+
+    amt = await getTokenBalance(program, auctionAta);
+    assert.equal(amt.amount, mintAmount);
+  });
+
+  it('register bidders', async () => {
+    deposit1 = lamports(1);
+    deposit2 = lamports(3);
+
+    await program.rpc.registerClockBidder(new anchor.BN(deposit1), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder1.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder1]
+    });
+
+    await program.rpc.registerClockBidder(new anchor.BN(deposit2), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder2.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder2]
+    });
+
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt - initialAuctionBalance, deposit1 + deposit2);
+  });
+
+  delay(auctionEndDelay, "delay for the clock to start");
+
+  it('drop out', async () => {
+    await program.rpc.dropOutClock({
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder1.publicKey,
+      },
+      signers: [bidder1]
+    });
+
+    auctionAccount = await program.account.clockAuction.fetch(auctionAddress);
+    assert.ok(auctionAccount.exitPrices[0].toNumber() > 0);
+    assert.equal(auctionAccount.exitPrices[1].toNumber(), 0);
+  });
+
+  it('last bidder cannot drop out', async () => {
+    await assert.rejects(program.rpc.dropOutClock({
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder2.publicKey,
+      },
+      signers: [bidder2]
+    }));
+  });
+
+  it('settle auction', async () => {
+    await program.rpc.settleClockAuction({
+      accounts: {
+        auction: auctionAddress,
+      },
+    });
+
+    auctionAccount = await program.account.clockAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.settled, true);
+    assert.equal(auctionAccount.winner.toBase58(), bidder2.publicKey.toBase58());
+    // the winner pays the price the other bidder dropped at
+    assert.equal(auctionAccount.clearingPrice.toNumber(), auctionAccount.exitPrices[0].toNumber());
+  });
+
+  it('reclaim losing deposit', async () => {
+    let bidderBalance = await getLamportBalance(program, bidder1.publicKey);
+
+    await program.rpc.reclaimClockDeposit({
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder1.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder1]
+    });
+
+    amt = await getLamportBalance(program, bidder1.publicKey);
+    assert.equal(amt - bidderBalance, deposit1);
+  });
+
+  it('withdraw winning bid', async () => {
+    let sellerBalance = await getLamportBalance(program, seller.publicKey);
+
+    await program.rpc.withdrawWinningBidClock({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - sellerBalance, auctionAccount.clearingPrice.toNumber());
+  });
+
+  it('withdraw item and unused deposit', async () => {
+    let bidderBalance = await getLamportBalance(program, bidder2.publicKey);
+
+    await program.rpc.withdrawItemClock({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        winner: bidder2.publicKey,
+        winnerAta: bidder2AtaAddress,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [bidder2]
+    });
+
+    amt = await getTokenBalance(program, bidder2AtaAddress);
+    assert.equal(amt.amount, mintAmount);
+
+    // bidder pays rent for the new ata out of the refund
+    amt = await getLamportBalance(program, bidder2.publicKey);
+    assert.ok(amt - bidderBalance <= deposit2 - auctionAccount.clearingPrice.toNumber());
+
+    // make sure auction hasn't kept any sol
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt, initialAuctionBalance);
+  });
+
+});
+
+describe('clock auction drop-out tie', () => {
+
+  let seller;
+  let bidder1;
+  let bidder2;
+  let mintOwner;
+  let mintAmount;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let floor;
+  let stepLength;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    bidder1 = anchor.web3.Keypair.generate();
+    bidder2 = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, bidder1.publicKey, lamports(5));
+    await airdrop(program, bidder2.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    mintAmount = 1;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    let auctionTitle = "clock tie test";
+    floor = lamports(0.5);
+    let increment = lamports(0.5);
+    // the drop-out lands in the first step and the second deposit runs out at the next one
+    stepLength = 15;
+    let startTime = Math.floor(Date.now() / 1000) + 5;
+    let bidderCap = 2;
+
+    [auctionAddress, bump, auctionAta] = await deriveClockAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createClockAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(floor),
+                                    new anchor.BN(increment),
+                                    new anchor.BN(stepLength),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(bidderCap),
+                                    new anchor.BN(mintAmount), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+  });
+
+  it('register bidders', async () => {
+    // the first registrant would win a plain tie at the floor
+    for (const [deposit, bidder] of [[lamports(1), bidder1], [lamports(0.7), bidder2]]) {
+      await program.rpc.registerClockBidder(new anchor.BN(deposit), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+  });
+
+  delay(auctionEndDelay, "delay for the clock to start");
+
+  it('drop out at the floor', async () => {
+    await program.rpc.dropOutClock({
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder1.publicKey,
+      },
+      signers: [bidder1]
+    });
+
+    auctionAccount = await program.account.clockAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.exitPrices[0].toNumber(), floor);
+  });
+
+  delay(stepLength * 1000, "delay for the clock to pass the last deposit");
+
+  it('the last bidder in still wins after their deposit runs out', async () => {
+    await program.rpc.settleClockAuction({
+      accounts: {
+        auction: auctionAddress,
+      },
+    });
+
+    auctionAccount = await program.account.clockAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.settled, true);
+    assert.equal(auctionAccount.winner.toBase58(), bidder2.publicKey.toBase58());
+    assert.equal(auctionAccount.clearingPrice.toNumber(), floor);
+  });
+
+});
+
+describe('raffle', () => {
+
+  let seller;
+  let buyer1;
+  let buyer2;
+  let mintOwner;
+  let mintAmount;
+  let mint;
+  let sellerAta;
+  let raffleAddress;
+  let bump;
+  let raffleAta;
+  let raffleAccount;
+  let ticketPrice;
+  let initialRaffleBalance;
+
+  let amt;
+
+  it('init raffle', async () => {
+    seller = anchor.web3.Keypair.generate();
+    buyer1 = anchor.web3.Keypair.generate();
+    buyer2 = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, buyer1.publicKey, lamports(5));
+    await airdrop(program, buyer2.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    mintAmount = 1;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    let raffleTitle = "raffle test";
+    ticketPrice = lamports(0.1);
+    let ticketCap = 10;
+    let minTickets = 2;
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let buyerCap = 2;
+
+    [raffleAddress, bump, raffleAta] = await deriveRaffle(program, seller.publicKey, mint.publicKey, raffleTitle);
+
+    await program.rpc.createRaffle(new anchor.BN(bump),
+                                    raffleTitle,
+                                    new anchor.BN(ticketPrice),
+                                    new anchor.BN(ticketCap),
+                                    new anchor.BN(minTickets),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(buyerCap),
+                                    new anchor.BN(mintAmount), {
+        accounts: {
+          auction: raffleAddress,
+          auctionAta: raffleAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+
+    initialRaffleBalance = await getLamportBalance(program, raffleAddress);
+
+    amt = await getTokenBalance(program, raffleAta);
+    assert.equal(amt.amount, mintAmount);
+  });
+
+  it('buy tickets', async () => {
+    await program.rpc.buyRaffleTickets(new anchor.BN(3), {
+      accounts: {
+        auction: raffleAddress,
+        buyer: buyer1.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer1]
+    });
+
+    await program.rpc.buyRaffleTickets(new anchor.BN(2), {
+      accounts: {
+        auction: raffleAddress,
+        buyer: buyer2.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer2]
+    });
+
+    raffleAccount = await program.account.raffle.fetch(raffleAddress);
+    assert.equal(raffleAccount.ticketsSold.toNumber(), 5);
+
+    amt = await getLamportBalance(program, raffleAddress);
+    assert.equal(amt - initialRaffleBalance, 5*ticketPrice);
+  });
+
+  it('ticket cap is respected', async () => {
+    await assert.rejects(program.rpc.buyRaffleTickets(new anchor.BN(6), {
+      accounts: {
+        auction: raffleAddress,
+        buyer: buyer1.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer1]
+    }));
+  });
+
+  delay(auctionEndDelay, "delay for raffle period to end");
+
+  it('commit draw slot', async () => {
+    await program.rpc.commitRaffleDraw({
+      accounts: {
+        auction: raffleAddress,
+        slotHashes: web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      },
+    });
+
+    raffleAccount = await program.account.raffle.fetch(raffleAddress);
+    assert.ok(raffleAccount.drawSlot.toNumber() > 0);
+  });
+
+  delay(auctionEndDelay, "delay for the draw slot to pass");
+
+  it('draw raffle', async () => {
+    await program.rpc.drawRaffle({
+      accounts: {
+        auction: raffleAddress,
+        slotHashes: web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      },
+    });
+
+    raffleAccount = await program.account.raffle.fetch(raffleAddress);
+    assert.equal(raffleAccount.drawn, true);
+    assert.ok([buyer1.publicKey.toBase58(), buyer2.publicKey.toBase58()].includes(raffleAccount.winner.toBase58()));
+  });
+
+  it('withdraw ticket proceeds', async () => {
+    let sellerBalance = await getLamportBalance(program, seller.publicKey);
+
+    await program.rpc.withdrawProceedsRaffle({
+      accounts: {
+        auction: raffleAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - sellerBalance, 5*ticketPrice);
+  });
+
+  it('withdraw winner spl tokens', async () => {
+    let winner = raffleAccount.winner.equals(buyer1.publicKey) ? buyer1 : buyer2;
+    let winnerAtaAddress = await serumAta.getAssociatedTokenAddress(winner.publicKey, mint.publicKey);
+
+    await program.rpc.withdrawItemRaffle({
+      accounts: {
+        auction: raffleAddress,
+        auctionAta: raffleAta,
+        winner: winner.publicKey,
+        winnerAta: winnerAtaAddress,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [winner]
+    });
+
+    amt = await getTokenBalance(program, winnerAtaAddress);
+    assert.equal(amt.amount, mintAmount);
+
+    // make sure raffle hasn't kept any sol
+    amt = await getLamportBalance(program, raffleAddress);
+    assert.equal(amt, initialRaffleBalance);
+  });
+
+});
+
+describe('open auction bundle', () => {
+
+  let seller;
+  let buyer;
+  let mintOwner;
+  let mintAmount;
+  let mint;
+  let bundleMints;
+  let bundleAmounts;
+  let sellerAta;
+  let buyerAtaAddress;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let winningBid;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    buyer = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, buyer.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    mintAmount = 1;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    buyerAtaAddress = await serumAta.getAssociatedTokenAddress(buyer.publicKey, mint.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    // two more mints sold in the same lot
+    bundleMints = [];
+    bundleAmounts = [1, 5];
+    for (let bundleAmount of bundleAmounts) {
+      let bundleMint = await splToken.Token.createMint(
+        program.provider.connection,
+        mintOwner,
+        mintOwner.publicKey,
+        null,
+        0,
+        splToken.TOKEN_PROGRAM_ID,
+      );
+      let bundleSellerAta = await bundleMint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+      await bundleMint.mintTo(bundleSellerAta.address, mintOwner.publicKey, [], bundleAmount);
+      bundleMints.push(bundleMint);
+    }
+
+    let auctionTitle = "bundle test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+
+    [auctionAddress, bump, auctionAta] = await deriveOpenAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    let remainingAccounts = [];
+    for (let bundleMint of bundleMints) {
+      remainingAccounts.push(
+        { pubkey: bundleMint.publicKey, isWritable: false, isSigner: false },
+        { pubkey: await serumAta.getAssociatedTokenAddress(seller.publicKey, bundleMint.publicKey), isWritable: true, isSigner: false },
+        { pubkey: await serumAta.getAssociatedTokenAddress(auctionAddress, bundleMint.publicKey), isWritable: true, isSigner: false },
+      );
+    }
+
+    await program.rpc.createOpenAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(lamports(0.05)),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(mintAmount),
+                                    openAuctionOptions({ bundleAmounts: bundleAmounts.map(x => new anchor.BN(x)) }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        remainingAccounts: remainingAccounts,
+        signers: [seller],
+    });
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.bundle.length, bundleMints.length);
+
+    for (let i = 0; i < bundleMints.length; i++) {
+      amt = await getTokenBalance(program, await serumAta.getAssociatedTokenAddress(auctionAddress, bundleMints[i].publicKey));
+      assert.equal(amt.amount, bundleAmounts[i]);
+    }
+  });
+
+  it('make winning bid', async () => {
+    winningBid = lamports(1);
+
+    await program.rpc.makeOpenBid(new anchor.BN(winningBid), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    });
+  });
+
+  delay(auctionEndDelay, "delay for auction period to end");
+
+  it('withdraw every mint in the lot', async () => {
+    let remainingAccounts = [];
+    for (let bundleMint of bundleMints) {
+      remainingAccounts.push(
+        { pubkey: bundleMint.publicKey, isWritable: false, isSigner: false },
+        { pubkey: await serumAta.getAssociatedTokenAddress(auctionAddress, bundleMint.publicKey), isWritable: true, isSigner: false },
+        { pubkey: await serumAta.getAssociatedTokenAddress(buyer.publicKey, bundleMint.publicKey), isWritable: true, isSigner: false },
+      );
+    }
+
+    await program.rpc.withdrawItemOpen({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        highestBidder: buyer.publicKey,
+        highestBidderAta: buyerAtaAddress,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      remainingAccounts: remainingAccounts,
+      signers: [buyer]
+    });
+
+    amt = await getTokenBalance(program, buyerAtaAddress);
+    assert.equal(amt.amount, mintAmount);
+
+    for (let i = 0; i < bundleMints.length; i++) {
+      amt = await getTokenBalance(program, await serumAta.getAssociatedTokenAddress(buyer.publicKey, bundleMints[i].publicKey));
+      assert.equal(amt.amount, bundleAmounts[i]);
+    }
+  });
+
+});
+
+describe('open auction bundle buy now', () => {
+
+  let seller;
+  let buyer;
+  let mintOwner;
+  let mintAmount;
+  let mint;
+  let bundleMints;
+  let bundleAmounts;
+  let sellerAta;
+  let buyerAtaAddress;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let buyNowPrice;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    buyer = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, buyer.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    mintAmount = 1;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    buyerAtaAddress = await serumAta.getAssociatedTokenAddress(buyer.publicKey, mint.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    bundleMints = [];
+    bundleAmounts = [3];
+    for (let bundleAmount of bundleAmounts) {
+      let bundleMint = await splToken.Token.createMint(
+        program.provider.connection,
+        mintOwner,
+        mintOwner.publicKey,
+        null,
+        0,
+        splToken.TOKEN_PROGRAM_ID,
+      );
+      let bundleSellerAta = await bundleMint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+      await bundleMint.mintTo(bundleSellerAta.address, mintOwner.publicKey, [], bundleAmount);
+      bundleMints.push(bundleMint);
+    }
+
+    let auctionTitle = "bundle buy now test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 60;
+    buyNowPrice = lamports(2);
+
+    [auctionAddress, bump, auctionAta] = await deriveOpenAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    let remainingAccounts = [];
+    for (let bundleMint of bundleMints) {
+      remainingAccounts.push(
+        { pubkey: bundleMint.publicKey, isWritable: false, isSigner: false },
+        { pubkey: await serumAta.getAssociatedTokenAddress(seller.publicKey, bundleMint.publicKey), isWritable: true, isSigner: false },
+        { pubkey: await serumAta.getAssociatedTokenAddress(auctionAddress, bundleMint.publicKey), isWritable: true, isSigner: false },
+      );
+    }
+
+    await program.rpc.createOpenAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(lamports(0.05)),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(mintAmount),
+                                    openAuctionOptions({
+                                      buyNow: { price: new anchor.BN(buyNowPrice), cutoffBps: new anchor.BN(10000) },
+                                      bundleAmounts: bundleAmounts.map(x => new anchor.BN(x)),
+                                    }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        remainingAccounts: remainingAccounts,
+        signers: [seller],
+    });
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.bundle.length, bundleMints.length);
+  });
+
+  it('buy now without the bundle accounts fails', async () => {
+    await assert.rejects(program.rpc.buyNowOpen({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        buyer: buyer.publicKey,
+        buyerAta: buyerAtaAddress,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [buyer]
+    }));
+  });
+
+  it('buy now moves every mint in the lot', async () => {
+    let remainingAccounts = [];
+    for (let bundleMint of bundleMints) {
+      remainingAccounts.push(
+        { pubkey: bundleMint.publicKey, isWritable: false, isSigner: false },
+        { pubkey: await serumAta.getAssociatedTokenAddress(auctionAddress, bundleMint.publicKey), isWritable: true, isSigner: false },
+        { pubkey: await serumAta.getAssociatedTokenAddress(buyer.publicKey, bundleMint.publicKey), isWritable: true, isSigner: false },
+      );
+    }
+
+    await program.rpc.buyNowOpen({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        buyer: buyer.publicKey,
+        buyerAta: buyerAtaAddress,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      remainingAccounts: remainingAccounts,
+      signers: [buyer]
+    });
+
+    auctionAccount = await program.account.openAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.itemWithdrawn, true);
+
+    amt = await getTokenBalance(program, buyerAtaAddress);
+    assert.equal(amt.amount, mintAmount);
+
+    for (let i = 0; i < bundleMints.length; i++) {
+      amt = await getTokenBalance(program, await serumAta.getAssociatedTokenAddress(buyer.publicKey, bundleMints[i].publicKey));
+      assert.equal(amt.amount, bundleAmounts[i]);
+    }
+  });
+
+});
+
+describe('package sealed auction', () => {
+
+  let seller;
+  let pairBuyer;
+  let singleBuyer;
+  let tailBuyer;
+  let mintOwner;
+  let lotMints;
+  let lotAmounts;
+  let auctionAddress;
+  let bump;
+  let auctionAccount;
+  let pairBid;
+  let singleBid;
+  let tailBid;
+  let initialAuctionBalance;
+
+  let amt;
+
+  async function lotAccounts(wallet: web3.PublicKey, lots: number[]) {
+    let accounts = [];
+    for (let i of lots) {
+      accounts.push(
+        { pubkey: lotMints[i].publicKey, isWritable: false, isSigner: false },
+        { pubkey: await serumAta.getAssociatedTokenAddress(auctionAddress, lotMints[i].publicKey), isWritable: true, isSigner: false },
+        { pubkey: await serumAta.getAssociatedTokenAddress(wallet, lotMints[i].publicKey), isWritable: true, isSigner: false },
+      );
+    }
+    return accounts;
+  }
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    pairBuyer = anchor.web3.Keypair.generate();
+    singleBuyer = anchor.web3.Keypair.generate();
+    tailBuyer = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, pairBuyer.publicKey, lamports(5));
+    await airdrop(program, singleBuyer.publicKey, lamports(5));
+    await airdrop(program, tailBuyer.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    // lots A, B, and C
+    lotMints = [];
+    lotAmounts = [1, 1, 1];
+    for (let lotAmount of lotAmounts) {
+      let lotMint = await splToken.Token.createMint(
+        program.provider.connection,
+        mintOwner,
+        mintOwner.publicKey,
+        null,
+        0,
+        splToken.TOKEN_PROGRAM_ID,
+      );
+      let sellerAta = await lotMint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+      await lotMint.mintTo(sellerAta.address, mintOwner.publicKey, [], lotAmount);
+      lotMints.push(lotMint);
+    }
+
+    let auctionTitle = "package sealed test";
+    let floor = lamports(0.1);
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let revealTime = Math.floor(Date.now() / 1000) + 10;
+    let bidderCap = 3;
+
+    [auctionAddress, bump] = await derivePackageSealedAuction(program, seller.publicKey, auctionTitle);
+
+    let remainingAccounts = [];
+    for (let lotMint of lotMints) {
+      remainingAccounts.push(
+        { pubkey: lotMint.publicKey, isWritable: false, isSigner: false },
+        { pubkey: await serumAta.getAssociatedTokenAddress(seller.publicKey, lotMint.publicKey), isWritable: true, isSigner: false },
+        { pubkey: await serumAta.getAssociatedTokenAddress(auctionAddress, lotMint.publicKey), isWritable: true, isSigner: false },
+      );
+    }
+
+    await program.rpc.createPackageSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(floor),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(bidderCap),
+                                    lotAmounts.map(x => new anchor.BN(x)), {
+        accounts: {
+          auction: auctionAddress,
+          owner: seller.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        remainingAccounts: remainingAccounts,
+        signers: [seller],
+    });
+
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+
+    auctionAccount = await program.account.packageSealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.lots.length, lotMints.length);
+  });
+
+  it('make package bids', async () => {
+    // A and B together for 3 SOL, A alone for 2 SOL, B and C together for 1.5 SOL
+    pairBid = { package: 0b011, bid: lamports(3), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(3) };
+    singleBid = { package: 0b001, bid: lamports(2), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2.5) };
+    tailBid = { package: 0b110, bid: lamports(1.5), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2) };
+
+    for (const [bid, bidder] of [[pairBid, pairBuyer], [singleBid, singleBuyer], [tailBid, tailBuyer]]) {
+      await program.rpc.makePackageSealedBid(packageBidHash(auctionAddress, bidder.publicKey, bid.package, bid.bid, bid.salt),
+                                             new anchor.BN(bid.deposit), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt - initialAuctionBalance, pairBid.deposit + singleBid.deposit + tailBid.deposit);
+  });
+
+  delay(auctionEndDelay, "delay for reveal period to start");
+
+  it('reveal package bids', async () => {
+    for (const [bid, bidder] of [[pairBid, pairBuyer], [singleBid, singleBuyer], [tailBid, tailBuyer]]) {
+      await program.rpc.revealPackageSealedBid(bid.package,
+                                               new anchor.BN(bid.bid),
+                                               Array.from(bid.salt), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+        },
+        signers: [bidder]
+      });
+    }
+  });
+
+  delay(revealPeriodEndDelay, "delay for reveal period to end");
+
+  it('settle auction', async () => {
+    await program.rpc.settlePackageSealedAuction({
+      accounts: {
+        auction: auctionAddress,
+      },
+    });
+
+    auctionAccount = await program.account.packageSealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.settled, true);
+    // A alone plus B and C together beats A and B together
+    assert.deepEqual(auctionAccount.winners, [false, true, true]);
+    assert.equal(auctionAccount.revenue.toNumber(), singleBid.bid + tailBid.bid);
+  });
+
+  it('withdraw lots and refunds', async () => {
+    for (const [bid, bidder, lots] of [[pairBid, pairBuyer, []], [singleBid, singleBuyer, [0]], [tailBid, tailBuyer, [1, 2]]]) {
+      let won = lots.length > 0;
+
+      await program.rpc.withdrawPackageSealed({
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        remainingAccounts: await lotAccounts(bidder.publicKey, lots),
+        signers: [bidder]
+      });
+
+      for (let i of lots) {
+        amt = await getTokenBalance(program, await serumAta.getAssociatedTokenAddress(bidder.publicKey, lotMints[i].publicKey));
+        assert.equal(amt.amount, lotAmounts[i]);
+      }
+      if (!won) {
+        auctionAccount = await program.account.packageSealedAuction.fetch(auctionAddress);
+        assert.equal(auctionAccount.fakeBids[0].toNumber(), 0);
+      }
+    }
+  });
+
+  it('withdraw proceeds', async () => {
+    let sellerBalance = await getLamportBalance(program, seller.publicKey);
+
+    await program.rpc.withdrawProceedsPackageSealed({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - sellerBalance, singleBid.bid + tailBid.bid);
+
+    // make sure auction hasn't kept any sol
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt, initialAuctionBalance);
+  });
+
+});
+
+describe('catalog auction', () => {
+
+  let seller;
+  let bidder1;
+  let bidder2;
+  let mintOwner;
+  let lotMints;
+  let lotAmount;
+  let auctionAddress;
+  let bump;
+  let auctionAccount;
+  let initialAuctionBalance;
+
+  let amt;
+
+  it('init auction and add lots', async () => {
+    seller = anchor.web3.Keypair.generate();
+    bidder1 = anchor.web3.Keypair.generate();
+    bidder2 = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, bidder1.publicKey, lamports(5));
+    await airdrop(program, bidder2.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    let auctionTitle = "catalog test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 10;
+    let bidderCap = 2;
+    let lotCap = 2;
+    let lotBidderCap = 2;
+
+    [auctionAddress, bump] = await deriveCatalogAuction(program, seller.publicKey, auctionTitle);
+
+    // 50 lots with room for 50 bidders on each lot doesn't fit in one account
+    await assert.rejects(program.rpc.createCatalogAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(lamports(0.05)),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(50),
+                                    new anchor.BN(50),
+                                    new anchor.BN(50), {
+        accounts: {
+          auction: auctionAddress,
+          owner: seller.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [seller],
+    }));
+
+    await program.rpc.createCatalogAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(lamports(0.05)),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(bidderCap),
+                                    new anchor.BN(lotCap),
+                                    new anchor.BN(lotBidderCap), {
+        accounts: {
+          auction: auctionAddress,
+          owner: seller.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [seller],
+    });
+
+    lotMints = [];
+    lotAmount = 1;
+    for (let i = 0; i < lotCap; i++) {
+      let lotMint = await splToken.Token.createMint(
+        program.provider.connection,
+        mintOwner,
+        mintOwner.publicKey,
+        null,
+        0,
+        splToken.TOKEN_PROGRAM_ID,
+      );
+      let sellerAta = await lotMint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+      await lotMint.mintTo(sellerAta.address, mintOwner.publicKey, [], lotAmount);
+      lotMints.push(lotMint);
+
+      await program.rpc.addCatalogLot(new anchor.BN(lotAmount), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: await serumAta.getAssociatedTokenAddress(auctionAddress, lotMint.publicKey),
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: lotMint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+      });
+    }
+
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+
+    auctionAccount = await program.account.catalogAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.lots.length, lotCap);
+  });
+
+  it('bid on separate lots', async () => {
+    await program.rpc.makeCatalogBid(new anchor.BN(0), new anchor.BN(lamports(1)), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder1.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder1]
+    });
+
+    // escrow on lot 0 doesn't count towards lot 1
+    await program.rpc.makeCatalogBid(new anchor.BN(1), new anchor.BN(lamports(0.5)), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder1.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder1]
+    });
+
+    auctionAccount = await program.account.catalogAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.lots[0].highestBid.toNumber(), lamports(1));
+    assert.equal(auctionAccount.lots[1].highestBid.toNumber(), lamports(0.5));
+
+    await program.rpc.makeCatalogBid(new anchor.BN(1), new anchor.BN(lamports(1)), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder2.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder2]
+    });
+
+    auctionAccount = await program.account.catalogAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.lots[0].highestBidder.toBase58(), bidder1.publicKey.toBase58());
+    assert.equal(auctionAccount.lots[1].highestBidder.toBase58(), bidder2.publicKey.toBase58());
+    assert.equal(auctionAccount.lots[0].bids.length, 1);
+    assert.equal(auctionAccount.lots[1].bids.length, 2);
+  });
+
+  it('reclaim outbid lot bid', async () => {
+    let bidderBalance = await getLamportBalance(program, bidder1.publicKey);
+
+    await program.rpc.reclaimCatalogBid(new anchor.BN(1), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder1.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder1]
+    });
+
+    amt = await getLamportBalance(program, bidder1.publicKey);
+    assert.equal(amt - bidderBalance, lamports(0.5));
+
+    auctionAccount = await program.account.catalogAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.lots[1].bids.length, 1);
+
+    // still leading on lot 0
+    await assert.rejects(program.rpc.reclaimCatalogBid(new anchor.BN(0), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder1.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder1]
+    }));
+  });
+
+  delay(auctionEndDelay + 5000, "delay for auction period to end");
+
+  it('withdraw each lot', async () => {
+    for (const [lot, winner] of [[0, bidder1], [1, bidder2]]) {
+      let winnerAtaAddress = await serumAta.getAssociatedTokenAddress(winner.publicKey, lotMints[lot].publicKey);
+
+      await program.rpc.withdrawItemCatalog(new anchor.BN(lot), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: await serumAta.getAssociatedTokenAddress(auctionAddress, lotMints[lot].publicKey),
+          highestBidder: winner.publicKey,
+          highestBidderAta: winnerAtaAddress,
+          mint: lotMints[lot].publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [winner]
+      });
+
+      amt = await getTokenBalance(program, winnerAtaAddress);
+      assert.equal(amt.amount, lotAmount);
+    }
+  });
+
+  it('withdraw each winning bid', async () => {
+    let sellerBalance = await getLamportBalance(program, seller.publicKey);
+
+    for (const lot of [0, 1]) {
+      await program.rpc.withdrawWinningBidCatalog(new anchor.BN(lot), {
+        accounts: {
+          auction: auctionAddress,
+          owner: seller.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [seller]
+      });
+    }
+
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - sellerBalance, lamports(2));
+
+    // make sure auction hasn't kept any sol
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt, initialAuctionBalance);
+  });
+
+});
+
+describe('series auction', () => {
+
+  let seller;
+  let bidder1;
+  let bidder2;
+  let mint;
+  let auctionAddress;
+  let bump;
+  let configAddress;
+  let configBump;
+  let auctionAccount;
+  let initialAuctionBalance;
+
+  let amt;
+
+  it('init series', async () => {
+    seller = anchor.web3.Keypair.generate();
+    bidder1 = anchor.web3.Keypair.generate();
+    bidder2 = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, bidder1.publicKey, lamports(5));
+    await airdrop(program, bidder2.publicKey, lamports(5));
+
+    let auctionTitle = "series test";
+    let roundDuration = 5;
+
+    [auctionAddress, bump] = await deriveSeriesAuction(program, seller.publicKey, auctionTitle);
+    [configAddress, configBump] = await deriveSeriesConfig(program, auctionAddress);
+
+    // the series pda mints every round
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      seller,
+      auctionAddress,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    await program.rpc.createSeriesAuction(new anchor.BN(bump),
+                                    new anchor.BN(configBump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(lamports(0.05)),
+                                    new anchor.BN(roundDuration),
+                                    new anchor.BN(0),
+                                    new anchor.BN(1), {
+        accounts: {
+          auction: auctionAddress,
+          config: configAddress,
+          owner: seller.publicKey,
+          mint: mint.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [seller],
+    });
+
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+
+    auctionAccount = await program.account.seriesAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.round.toNumber(), 0);
+    assert.equal(auctionAccount.roundEnd.toNumber() - auctionAccount.roundStart.toNumber(), roundDuration);
+    assert.equal(auctionAccount.config.toBase58(), configAddress.toBase58());
+
+    let configAccount = await program.account.seriesConfig.fetch(configAddress);
+    assert.equal(configAccount.roundDuration.toNumber(), roundDuration);
+  });
+
+  delay(1500, "delay for round to start");
+
+  it('outbid refunds previous bidder', async () => {
+    await program.rpc.makeSeriesBid(new anchor.BN(lamports(0.5)), {
+      accounts: {
+        auction: auctionAddress,
+        config: configAddress,
+        bidder: bidder1.publicKey,
+        previousBidder: bidder1.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder1]
+    });
+
+    let bidderBalance = await getLamportBalance(program, bidder1.publicKey);
+
+    // wrong previous bidder
+    await assert.rejects(program.rpc.makeSeriesBid(new anchor.BN(lamports(1)), {
+      accounts: {
+        auction: auctionAddress,
+        config: configAddress,
+        bidder: bidder2.publicKey,
+        previousBidder: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder2]
+    }));
+
+    await program.rpc.makeSeriesBid(new anchor.BN(lamports(1)), {
+      accounts: {
+        auction: auctionAddress,
+        config: configAddress,
+        bidder: bidder2.publicKey,
+        previousBidder: bidder1.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder2]
+    });
+
+    amt = await getLamportBalance(program, bidder1.publicKey);
+    assert.equal(amt - bidderBalance, lamports(0.5));
+
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt - initialAuctionBalance, lamports(1));
+  });
+
+  delay(auctionEndDelay, "delay for round to end");
+
+  it('settle rejects a token account other than the winner\'s ata', async () => {
+    let foreignAta = await mint.getOrCreateAssociatedAccountInfo(bidder1.publicKey);
+
+    await assert.rejects(program.rpc.settleSeriesRound({
+      accounts: {
+        auction: auctionAddress,
+        config: configAddress,
+        owner: seller.publicKey,
+        mint: mint.publicKey,
+        highestBidder: bidder2.publicKey,
+        highestBidderAta: foreignAta.address,
+        payer: bidder1.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [bidder1]
+    }));
+
+    amt = await getTokenBalance(program, foreignAta.address);
+    assert.equal(amt.amount, 0);
+  });
+
+  it('settle mints to winner and starts next round', async () => {
+    let sellerBalance = await getLamportBalance(program, seller.publicKey);
+    let winnerAtaAddress = await serumAta.getAssociatedTokenAddress(bidder2.publicKey, mint.publicKey);
+
+    // anyone can settle
+    await program.rpc.settleSeriesRound({
+      accounts: {
+        auction: auctionAddress,
+        config: configAddress,
+        owner: seller.publicKey,
+        mint: mint.publicKey,
+        highestBidder: bidder2.publicKey,
+        highestBidderAta: winnerAtaAddress,
+        payer: bidder1.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [bidder1]
+    });
+
+    amt = await getTokenBalance(program, winnerAtaAddress);
+    assert.equal(amt.amount, 1);
+
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - sellerBalance, lamports(1));
+
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt, initialAuctionBalance);
+
+    auctionAccount = await program.account.seriesAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.round.toNumber(), 1);
+    assert.equal(auctionAccount.highestBid.toNumber(), 0);
+    assert.ok(!auctionAccount.roundSettled);
+  });
+
+  it('stop series', async () => {
+    await program.rpc.stopSeriesAuction({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+      },
+      signers: [seller]
+    });
+
+    auctionAccount = await program.account.seriesAuction.fetch(auctionAddress);
+    assert.ok(auctionAccount.stopped);
+  });
+
+  delay(auctionEndDelay, "delay for round to end");
+
+  it('settle round with no bids ends stopped series', async () => {
+    await program.rpc.settleSeriesRound({
+      accounts: {
+        auction: auctionAddress,
+        config: configAddress,
+        owner: seller.publicKey,
+        mint: mint.publicKey,
+        highestBidder: seller.publicKey,
+        highestBidderAta: await serumAta.getAssociatedTokenAddress(seller.publicKey, mint.publicKey),
+        payer: seller.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [seller]
+    });
+
+    auctionAccount = await program.account.seriesAuction.fetch(auctionAddress);
+    assert.ok(auctionAccount.roundSettled);
+
+    amt = await mint.getMintInfo();
+    assert.equal(amt.supply.toNumber(), 1);
+
+    await assert.rejects(program.rpc.makeSeriesBid(new anchor.BN(lamports(1)), {
+      accounts: {
+        auction: auctionAddress,
+        config: configAddress,
+        bidder: bidder1.publicKey,
+        previousBidder: bidder1.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder1]
+    }));
+  });
+
+});
+
+describe('open auction multi-unit', () => {
+
+  let seller;
+  let bidder1;
+  let bidder2;
+  let bidder3;
+  let mintOwner;
+  let mintAmount;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let initialAuctionBalance;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    bidder1 = anchor.web3.Keypair.generate();
+    bidder2 = anchor.web3.Keypair.generate();
+    bidder3 = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, bidder1.publicKey, lamports(5));
+    await airdrop(program, bidder2.publicKey, lamports(5));
+    await airdrop(program, bidder3.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    mintAmount = 2;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    let auctionTitle = "multi-unit open test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let units = 2;
+
+    [auctionAddress, bump, auctionAta] = await deriveOpenAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createOpenAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(3),
+                                    new anchor.BN(mintAmount),
+                                    openAuctionOptions({ units: new anchor.BN(units) }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+
+    amt = await getTokenBalance(program, auctionAta);
+    assert.equal(amt.amount, mintAmount);
+  });
+
+  it('bids must beat the lowest winner', async () => {
+    for (const [bidder, bid] of [[bidder1, 1], [bidder2, 0.5]]) {
+      await program.rpc.makeOpenBid(new anchor.BN(lamports(bid)), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+
+    // only matches bidder2 plus the increment
+    await assert.rejects(program.rpc.makeOpenBid(new anchor.BN(lamports(0.6)), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder3.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder3]
+    }));
+
+    await program.rpc.makeOpenBid(new anchor.BN(lamports(0.7)), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder3.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder3]
+    });
+  });
+
+  it('only bidders outside the winning set can reclaim', async () => {
+    let bidderBalance = await getLamportBalance(program, bidder2.publicKey);
+
+    await program.rpc.reclaimOpenBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder2.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder2]
+    });
+
+    amt = await getLamportBalance(program, bidder2.publicKey);
+    assert.equal(amt - bidderBalance, lamports(0.5));
+
+    for (const bidder of [bidder1, bidder3]) {
+      await assert.rejects(program.rpc.reclaimOpenBid({
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      }));
+    }
+  });
+
+  delay(auctionEndDelay, "delay for auction period to end");
+
+  it('each winner withdraws one unit', async () => {
+    for (const bidder of [bidder1, bidder3]) {
+      let bidderAtaAddress = await serumAta.getAssociatedTokenAddress(bidder.publicKey, mint.publicKey);
+
+      await program.rpc.withdrawUnitOpen({
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          bidder: bidder.publicKey,
+          bidderAta: bidderAtaAddress,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [bidder]
+      });
+
+      amt = await getTokenBalance(program, bidderAtaAddress);
+      assert.equal(amt.amount, 1);
+    }
+
+    await assert.rejects(program.rpc.withdrawUnitOpen({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        bidder: bidder2.publicKey,
+        bidderAta: await serumAta.getAssociatedTokenAddress(bidder2.publicKey, mint.publicKey),
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [bidder2]
+    }));
+  });
+
+  it('withdraw winning bids', async () => {
+    let sellerBalance = await getLamportBalance(program, seller.publicKey);
+
+    await program.rpc.withdrawWinningBidOpen({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    // each winner pays their own bid
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - sellerBalance, lamports(1.7));
+
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt, initialAuctionBalance);
+
+    // every unit was sold
+    await assert.rejects(program.rpc.reclaimItemOpen({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        owner: seller.publicKey,
+        ownerAta: sellerAta.address,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [seller]
+    }));
+  });
+
+});
+
+describe('multi-unit sealed auction vcg', () => {
+
+  let seller;
+  let bidders;
+  let mintOwner;
+  let mintAmount;
+  let units;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let bids;
+  let initialAuctionBalance;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    bidders = [anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate(), anchor.web3.Keypair.generate()];
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(10));
+    await airdrop(program, mintOwner.publicKey, lamports(10));
+    for (const bidder of bidders) {
+      await airdrop(program, bidder.publicKey, lamports(10));
+    }
+
+    units = 3;
+    mintAmount = 3;
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], mintAmount);
+
+    let auctionTitle = "vcg test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let revealTime = Math.floor(Date.now() / 1000) + 10;
+
+    [auctionAddress, bump, auctionAta] = await deriveMultiUnitSealedAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    // over the vcg bidder cap
+    await assert.rejects(program.rpc.createMultiUnitSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    { vcg: {} },
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(17),
+                                    new anchor.BN(mintAmount),
+                                    new anchor.BN(units), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    }));
+
+    await program.rpc.createMultiUnitSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    { vcg: {} },
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(3),
+                                    new anchor.BN(mintAmount),
+                                    new anchor.BN(units), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+  });
+
+  it('make sealed bids', async () => {
+    // two units at 1 SOL, two units at 0.6 SOL, and one unit at 0.3 SOL
+    bids = [
+      { units: 2, price: lamports(1), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2.5) },
+      { units: 2, price: lamports(0.6), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2.5) },
+      { units: 1, price: lamports(0.3), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2.5) },
+    ];
+
+    for (let i = 0; i < bids.length; i++) {
+      await program.rpc.makeMultiUnitSealedBid(multiUnitBidHash(auctionAddress, bidders[i].publicKey, bids[i].units, bids[i].price, bids[i].salt),
+                                               new anchor.BN(bids[i].deposit), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidders[i].publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidders[i]]
+      });
+    }
+  });
+
+  delay(auctionEndDelay, "delay for reveal period to start");
+
+  it('reveal sealed bids', async () => {
+    for (let i = 0; i < bids.length; i++) {
+      await program.rpc.revealMultiUnitSealedBid(new anchor.BN(bids[i].units),
+                                                 new anchor.BN(bids[i].price),
+                                                 Array.from(bids[i].salt), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidders[i].publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidders[i]]
+      });
+    }
+  });
+
+  delay(revealPeriodEndDelay, "delay for reveal period to end");
+
+  it('settle auction', async () => {
+    await program.rpc.settleMultiUnitSealedAuction({
+      accounts: {
+        auction: auctionAddress,
+      },
+    });
+
+    auctionAccount = await program.account.multiUnitSealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.allocations[0].toNumber(), 2);
+    assert.equal(auctionAccount.allocations[1].toNumber(), 1);
+    assert.equal(auctionAccount.allocations[2].toNumber(), 0);
+    // the first bid displaces a 0.6 and a 0.3 unit, the second displaces a 0.3 unit
+    assert.equal(auctionAccount.payments[0].toNumber(), lamports(0.9));
+    assert.equal(auctionAccount.payments[1].toNumber(), lamports(0.3));
+    assert.equal(auctionAccount.payments[2].toNumber(), 0);
+  });
+
+  it('withdraw units and refunds', async () => {
+    let payments = [lamports(0.9), lamports(0.3), 0];
+
+    for (let i = 0; i < bids.length; i++) {
+      let bidderAta = await serumAta.getAssociatedTokenAddress(bidders[i].publicKey, mint.publicKey);
+      let initialBalance = await getLamportBalance(program, bidders[i].publicKey);
+
+      await program.rpc.withdrawMultiUnitSealed({
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          bidder: bidders[i].publicKey,
+          bidderAta: bidderAta,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [bidders[i]]
+      });
+
+      amt = await getLamportBalance(program, bidders[i].publicKey);
+      // refunded deposit minus the vcg payment minus cost to create ATA
+      assert.ok(amt - initialBalance > (bids[i].deposit - payments[i] - 10000000));
+      assert.ok(amt - initialBalance <= bids[i].deposit - payments[i]);
+    }
+  });
+
+  it('withdraw proceeds', async () => {
+    let initialBalance = await getLamportBalance(program, seller.publicKey);
+
+    await program.rpc.withdrawProceedsMultiUnitSealed({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - initialBalance, lamports(1.2));
+
+    // make sure auction hasn't kept any sol
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt, initialAuctionBalance);
+  });
+
+});
+
+describe('sealed auction commitment v2', () => {
+
+  let seller;
+  let loser;
+  let buyer;
+  let mintOwner;
+  let mint;
+  let sellerAta;
+  let buyerAtaAddress;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let losingBid;
+  let winningBid;
+  let initialAuctionBalance;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    loser = anchor.web3.Keypair.generate();
+    buyer = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(10));
+    await airdrop(program, loser.publicKey, lamports(10));
+    await airdrop(program, buyer.publicKey, lamports(10));
+    await airdrop(program, mintOwner.publicKey, lamports(10));
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    buyerAtaAddress = await serumAta.getAssociatedTokenAddress(buyer.publicKey, mint.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], 1);
+
+    let auctionTitle = "commitment v2 test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let revealTime = Math.floor(Date.now() / 1000) + 10;
+
+    [auctionAddress, bump, auctionAta] = await deriveSealedAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    true,
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(1),
+                                    sealedAuctionOptions(), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.commitmentVersion, 2);
+  });
+
+  it('make sealed bids', async () => {
+    losingBid = { bid: lamports(1), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(1.5) };
+    winningBid = { bid: lamports(2), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2.5) };
+
+    for (const [bid, bidder] of [[losingBid, loser], [winningBid, buyer]]) {
+      await program.rpc.makeSealedBid(bidHashV2(auctionAddress, bidder.publicKey, bid.bid, bid.salt),
+                                      new anchor.BN(bid.deposit),
+                                      Buffer.from([]), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+  });
+
+  delay(auctionEndDelay, "delay for reveal period to start");
+
+  it('v1 reveal is rejected', async () => {
+    await assert.rejects(program.rpc.revealSealedBid(new anchor.BN(winningBid.bid),
+                                                     new anchor.BN(0), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    }));
+  });
+
+  it('commitment is bound to the bidder', async () => {
+    // the winner's bid and salt don't open the loser's commitment
+    await assert.rejects(program.rpc.revealSealedBidV2(new anchor.BN(winningBid.bid),
+                                                       Array.from(winningBid.salt), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: loser.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [loser]
+    }));
+  });
+
+  it('reveal sealed bids', async () => {
+    await program.rpc.revealSealedBidV2(new anchor.BN(winningBid.bid),
+                                        Array.from(winningBid.salt), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    });
+
+    let loserBalance = await getLamportBalance(program, loser.publicKey);
+
+    await program.rpc.revealSealedBidV2(new anchor.BN(losingBid.bid),
+                                        Array.from(losingBid.salt), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: loser.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [loser]
+    });
+
+    amt = await getLamportBalance(program, loser.publicKey);
+    assert.equal(amt - loserBalance, losingBid.deposit);
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.highestBidder.toBase58(), buyer.publicKey.toBase58());
+    assert.equal(auctionAccount.highestBid.toNumber(), winningBid.bid);
+  });
+
+  delay(revealPeriodEndDelay, "delay for reveal period to end");
+
+  it('withdraw winning bid and spl tokens', async () => {
+    let sellerBalance = await getLamportBalance(program, seller.publicKey);
+
+    await program.rpc.withdrawWinningBidSealed({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - sellerBalance, winningBid.bid);
+
+    await program.rpc.withdrawItemSealed({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        highestBidder: buyer.publicKey,
+        highestBidderAta: buyerAtaAddress,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [buyer]
+    });
+
+    amt = await getTokenBalance(program, buyerAtaAddress);
+    assert.equal(amt.amount, 1);
+
+    // make sure auction hasn't kept any sol
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt, initialAuctionBalance);
+  });
+
+});
+
+describe('sealed auction non-reveal penalty', () => {
+
+  let seller;
+  let treasury;
+  let buyer;
+  let ghost;
+  let sleeper;
+  let mintOwner;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let winningBid;
+  let ghostBid;
+  let sleeperBid;
+  let penaltyBps;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    treasury = anchor.web3.Keypair.generate();
+    buyer = anchor.web3.Keypair.generate();
+    ghost = anchor.web3.Keypair.generate();
+    sleeper = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(10));
+    await airdrop(program, buyer.publicKey, lamports(10));
+    await airdrop(program, ghost.publicKey, lamports(10));
+    await airdrop(program, sleeper.publicKey, lamports(10));
+    await airdrop(program, mintOwner.publicKey, lamports(10));
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], 1);
+
+    let auctionTitle = "penalty test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let revealTime = Math.floor(Date.now() / 1000) + 10;
+    penaltyBps = 2000;
+
+    [auctionAddress, bump, auctionAta] = await deriveSealedAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    // forfeits could never be withdrawn without a recipient
+    await assert.rejects(program.rpc.createSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    true,
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(3),
+                                    new anchor.BN(1),
+                                    sealedAuctionOptions({
+                                      penalty: { bps: new anchor.BN(penaltyBps), bond: new anchor.BN(0), recipient: web3.PublicKey.default },
+                                    }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    }));
+
+    await program.rpc.createSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    true,
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(3),
+                                    new anchor.BN(1),
+                                    sealedAuctionOptions({
+                                      penalty: { bps: new anchor.BN(penaltyBps), bond: new anchor.BN(0), recipient: treasury.publicKey },
+                                    }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+  });
+
+  it('make sealed bids', async () => {
+    winningBid = { bid: lamports(2), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2.5) };
+    ghostBid = { bid: lamports(1), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(1) };
+    sleeperBid = { bid: lamports(0.5), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(1.5) };
+
+    for (const [bid, bidder] of [[winningBid, buyer], [ghostBid, ghost], [sleeperBid, sleeper]]) {
+      await program.rpc.makeSealedBid(bidHashV2(auctionAddress, bidder.publicKey, bid.bid, bid.salt),
+                                      new anchor.BN(bid.deposit),
+                                      Buffer.from([]), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+  });
+
+  delay(auctionEndDelay, "delay for reveal period to start");
+
+  it('unrevealed bid is locked during the reveal period', async () => {
+    await program.rpc.revealSealedBidV2(new anchor.BN(winningBid.bid),
+                                        Array.from(winningBid.salt), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    });
+
+    await assert.rejects(program.rpc.reclaimSealedBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: ghost.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [ghost]
+    }));
+  });
+
+  delay(revealPeriodEndDelay, "delay for reveal period to end");
+
+  it('unrevealed bid forfeits the penalty', async () => {
+    let ghostBalance = await getLamportBalance(program, ghost.publicKey);
+    let penalty = ghostBid.deposit * penaltyBps / 10000;
+
+    await program.rpc.reclaimSealedBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: ghost.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [ghost]
+    });
+
+    amt = await getLamportBalance(program, ghost.publicKey);
+    assert.equal(amt - ghostBalance, ghostBid.deposit - penalty);
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.forfeited.toNumber(), penalty);
+  });
+
+  it('anyone can sweep a bidder who never comes back', async () => {
+    let sleeperBalance = await getLamportBalance(program, sleeper.publicKey);
+    let penalty = sleeperBid.deposit * penaltyBps / 10000;
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    let forfeited = auctionAccount.forfeited.toNumber();
+
+    // revealed bids can't be swept
+    await assert.rejects(program.rpc.forfeitUnrevealedSealed({
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+    }));
+
+    await program.rpc.forfeitUnrevealedSealed({
+      accounts: {
+        auction: auctionAddress,
+        bidder: sleeper.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+    });
+
+    amt = await getLamportBalance(program, sleeper.publicKey);
+    assert.equal(amt - sleeperBalance, sleeperBid.deposit - penalty);
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.forfeited.toNumber(), forfeited + penalty);
+    assert.equal(auctionAccount.bidders.length, 1);
+  });
+
+  it('penalty recipient withdraws the forfeits', async () => {
+    let forfeited = (ghostBid.deposit + sleeperBid.deposit) * penaltyBps / 10000;
+
+    // only the penalty recipient can withdraw it
+    await assert.rejects(program.rpc.withdrawForfeitsSealed({
+      accounts: {
+        auction: auctionAddress,
+        penaltyRecipient: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    }));
+
+    await program.rpc.withdrawForfeitsSealed({
+      accounts: {
+        auction: auctionAddress,
+        penaltyRecipient: treasury.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [treasury]
+    });
+
+    amt = await getLamportBalance(program, treasury.publicKey);
+    assert.equal(amt, forfeited);
+  });
+
+  it('revealed winner is unaffected', async () => {
+    let sellerBalance = await getLamportBalance(program, seller.publicKey);
+
+    await program.rpc.withdrawWinningBidSealed({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - sellerBalance, winningBid.bid);
+  });
+
+});
+
+describe('sealed auction bid update', () => {
+
+  let seller;
+  let buyer;
+  let mintOwner;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let firstBid;
+  let secondBid;
+  let initialAuctionBalance;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    buyer = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(10));
+    await airdrop(program, buyer.publicKey, lamports(10));
+    await airdrop(program, mintOwner.publicKey, lamports(10));
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], 1);
+
+    let auctionTitle = "bid update test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let revealTime = Math.floor(Date.now() / 1000) + 10;
+
+    [auctionAddress, bump, auctionAta] = await deriveSealedAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    // deposits can only go up
+    await program.rpc.createSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    true,
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(1),
+                                    new anchor.BN(1),
+                                    sealedAuctionOptions(), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+
+    initialAuctionBalance = await getLamportBalance(program, auctionAddress);
+  });
+
+  it('make and raise sealed bid', async () => {
+    firstBid = { bid: lamports(1), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(1.5) };
+    secondBid = { bid: lamports(2), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2.5) };
+
+    await program.rpc.makeSealedBid(bidHashV2(auctionAddress, buyer.publicKey, firstBid.bid, firstBid.salt),
+                                    new anchor.BN(firstBid.deposit),
+                                    Buffer.from([]), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    });
+
+    // lowering the deposit is disabled
+    await assert.rejects(program.rpc.updateSealedBid(bidHashV2(auctionAddress, buyer.publicKey, firstBid.bid, firstBid.salt),
+                                                     new anchor.BN(lamports(1.2)),
+                                                     Buffer.from([]), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    }));
+
+    // replaces the commitment in the same bidder slot
+    await program.rpc.updateSealedBid(bidHashV2(auctionAddress, buyer.publicKey, secondBid.bid, secondBid.salt),
+                                      new anchor.BN(secondBid.deposit),
+                                      Buffer.from([]), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    });
+
+    amt = await getLamportBalance(program, auctionAddress);
+    assert.equal(amt - initialAuctionBalance, secondBid.deposit);
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.bidders.length, 1);
+    assert.equal(auctionAccount.fakeBids[0].toNumber(), secondBid.deposit);
+  });
+
+  delay(auctionEndDelay, "delay for reveal period to start");
+
+  it('only the new commitment reveals', async () => {
+    await assert.rejects(program.rpc.revealSealedBidV2(new anchor.BN(firstBid.bid),
+                                                       Array.from(firstBid.salt), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    }));
+
+    await program.rpc.revealSealedBidV2(new anchor.BN(secondBid.bid),
+                                        Array.from(secondBid.salt), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    });
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.highestBid.toNumber(), secondBid.bid);
+  });
+
+});
+
+describe('sealed auction auctioneer reveal', () => {
+
+  let seller;
+  let buyer;
+  let loser;
+  let auctioneer;
+  let mintOwner;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let winningBid;
+  let losingBid;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    buyer = anchor.web3.Keypair.generate();
+    loser = anchor.web3.Keypair.generate();
+    auctioneer = nacl.box.keyPair();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(10));
+    await airdrop(program, buyer.publicKey, lamports(10));
+    await airdrop(program, loser.publicKey, lamports(10));
+    await airdrop(program, mintOwner.publicKey, lamports(10));
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], 1);
+
+    let auctionTitle = "auctioneer test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let revealTime = Math.floor(Date.now() / 1000) + 10;
+
+    [auctionAddress, bump, auctionAta] = await deriveSealedAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    true,
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(1),
+                                    sealedAuctionOptions({ auctioneer: new web3.PublicKey(auctioneer.publicKey) }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+  });
+
+  it('make encrypted sealed bids', async () => {
+    winningBid = { bid: lamports(2), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2.5) };
+    losingBid = { bid: lamports(1), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(1.5) };
+
+    // too long to be a boxed bid
+    await assert.rejects(program.rpc.makeSealedBid(bidHashV2(auctionAddress, buyer.publicKey, winningBid.bid, winningBid.salt),
+                                                   new anchor.BN(winningBid.deposit),
+                                                   Buffer.alloc(129), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    }));
+
+    for (const [bid, bidder] of [[winningBid, buyer], [losingBid, loser]]) {
+      await program.rpc.makeSealedBid(bidHashV2(auctionAddress, bidder.publicKey, bid.bid, bid.salt),
+                                      new anchor.BN(bid.deposit),
+                                      encryptBid(auctioneer.publicKey, bid.bid, bid.salt), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+  });
+
+  delay(auctionEndDelay, "delay for reveal period to start");
+
+  it('bidder can still reveal themselves', async () => {
+    await program.rpc.revealSealedBidV2(new anchor.BN(winningBid.bid),
+                                        Array.from(winningBid.salt), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    });
+  });
+
+  it('keeper reveals from the stored ciphertext', async () => {
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    let index = auctionAccount.bidders.findIndex(x => x.equals(loser.publicKey));
+    let [bid, salt] = decryptBid(auctioneer.secretKey, Buffer.from(auctionAccount.ciphertexts[index]));
+    assert.equal(bid.toNumber(), losingBid.bid);
+
+    // no signer is needed, the stored hash rejects any other bid
+    await assert.rejects(program.rpc.revealSealedBidFor(bid.subn(1), salt, {
+      accounts: {
+        auction: auctionAddress,
+        bidder: loser.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+    }));
+
+    let loserBalance = await getLamportBalance(program, loser.publicKey);
+
+    await program.rpc.revealSealedBidFor(bid, salt, {
+      accounts: {
+        auction: auctionAddress,
+        bidder: loser.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+    });
+
+    // the losing bidder is refunded without coming back
+    amt = await getLamportBalance(program, loser.publicKey);
+    assert.equal(amt - loserBalance, losingBid.deposit);
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.highestBidder.toBase58(), buyer.publicKey.toBase58());
+    assert.equal(auctionAccount.bidders.length, 1);
+  });
+
+});
+
+describe('sealed auction phases', () => {
+
+  let seller;
+  let buyer;
+  let loser;
+  let mintOwner;
+  let mint;
+  let sellerAta;
+  let buyerAtaAddress;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let winningBid;
+  let losingBid;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    buyer = anchor.web3.Keypair.generate();
+    loser = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(10));
+    await airdrop(program, buyer.publicKey, lamports(10));
+    await airdrop(program, loser.publicKey, lamports(10));
+    await airdrop(program, mintOwner.publicKey, lamports(10));
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], 1);
+
+    let auctionTitle = "phase test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let revealTime = Math.floor(Date.now() / 1000) + 10;
+
+    [auctionAddress, bump, auctionAta] = await deriveSealedAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+    buyerAtaAddress = await serumAta.getAssociatedTokenAddress(buyer.publicKey, mint.publicKey);
+
+    await program.rpc.createSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    true,
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(1),
+                                    sealedAuctionOptions(), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.ok('bidding' in auctionAccount.phase);
+  });
+
+  it('bids can only be made while bidding', async () => {
+    winningBid = { bid: lamports(2), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(3) };
+    losingBid = { bid: lamports(1), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(1) };
+
+    for (const [bid, bidder] of [[losingBid, loser], [winningBid, buyer]]) {
+      await program.rpc.makeSealedBid(bidHashV2(auctionAddress, bidder.publicKey, bid.bid, bid.salt),
+                                      new anchor.BN(bid.deposit),
+                                      Buffer.from([]), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+
+    await assert.rejects(program.rpc.revealSealedBidV2(new anchor.BN(winningBid.bid),
+                                                       Array.from(winningBid.salt), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    }));
+
+    await assert.rejects(program.rpc.withdrawWinningBidSealed({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    }));
+  });
+
+  delay(auctionEndDelay, "delay for reveal period to start");
+
+  it('reveals can only be made while revealing', async () => {
+    await assert.rejects(program.rpc.cancelSealedAuction({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    }));
+
+    for (const [bid, bidder] of [[losingBid, loser], [winningBid, buyer]]) {
+      await program.rpc.revealSealedBidV2(new anchor.BN(bid.bid),
+                                          Array.from(bid.salt), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.ok('revealing' in auctionAccount.phase);
+    assert.equal(auctionAccount.highestBidder.toBase58(), buyer.publicKey.toBase58());
+  });
+
+  it('highest bidder cannot reclaim their deposit', async () => {
+    await assert.rejects(program.rpc.reclaimSealedBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    }));
+
+    // the outbid bidder revealed first so they reclaim the full deposit
+    let loserBalance = await getLamportBalance(program, loser.publicKey);
+
+    await program.rpc.reclaimSealedBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: loser.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [loser]
+    });
+
+    amt = await getLamportBalance(program, loser.publicKey);
+    assert.equal(amt - loserBalance, losingBid.deposit);
+  });
+
+  delay(revealPeriodEndDelay, "delay for reveal period to end");
+
+  it('settles once the item and winning bid are withdrawn', async () => {
+    await program.rpc.withdrawItemSealed({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        highestBidder: buyer.publicKey,
+        highestBidderAta: buyerAtaAddress,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [buyer]
+    });
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.ok('settling' in auctionAccount.phase);
+
+    await program.rpc.withdrawWinningBidSealed({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.ok('settled' in auctionAccount.phase);
+
+    await assert.rejects(program.rpc.withdrawWinningBidSealed({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    }));
+
+    // the winner's refund already went out with the item
+    await assert.rejects(program.rpc.reclaimSealedBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    }));
+  });
+
+});
+
+describe('sealed auction deposit tiers', () => {
+
+  let seller;
+  let buyer;
+  let lowballer;
+  let mintOwner;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let winningBid;
+  let lowBid;
+  let tiers;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    buyer = anchor.web3.Keypair.generate();
+    lowballer = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(10));
+    await airdrop(program, buyer.publicKey, lamports(10));
+    await airdrop(program, lowballer.publicKey, lamports(10));
+    await airdrop(program, mintOwner.publicKey, lamports(10));
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], 1);
+
+    let auctionTitle = "tier test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let revealTime = Math.floor(Date.now() / 1000) + 10;
+    tiers = [lamports(1), lamports(2), lamports(4)];
+
+    [auctionAddress, bump, auctionAta] = await deriveSealedAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    true,
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(1),
+                                    sealedAuctionOptions({
+                                      deposits: { tiers: tiers.map(tier => new anchor.BN(tier)), allowDecrease: false },
+                                    }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.deepEqual(auctionAccount.depositTiers.map(tier => tier.toNumber()), tiers);
+  });
+
+  it('deposits must be a tier', async () => {
+    winningBid = { bid: lamports(1.5), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: tiers[1] };
+    // covered by the 1 SOL tier, so depositing 4 SOL only pretends to be a high bid
+    lowBid = { bid: lamports(0.5), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: tiers[2] };
+
+    await assert.rejects(program.rpc.makeSealedBid(bidHashV2(auctionAddress, buyer.publicKey, winningBid.bid, winningBid.salt),
+                                                   new anchor.BN(winningBid.bid),
+                                                   Buffer.from([]), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    }));
+
+    for (const [bid, bidder] of [[winningBid, buyer], [lowBid, lowballer]]) {
+      await program.rpc.makeSealedBid(bidHashV2(auctionAddress, bidder.publicKey, bid.bid, bid.salt),
+                                      new anchor.BN(bid.deposit),
+                                      Buffer.from([]), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+  });
+
+  delay(auctionEndDelay, "delay for reveal period to start");
+
+  it('revealed bids must fit their tier', async () => {
+    await assert.rejects(program.rpc.revealSealedBidV2(new anchor.BN(lowBid.bid),
+                                                       Array.from(lowBid.salt), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: lowballer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [lowballer]
+    }));
+
+    await program.rpc.revealSealedBidV2(new anchor.BN(winningBid.bid),
+                                        Array.from(winningBid.salt), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    });
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.highestBidder.toBase58(), buyer.publicKey.toBase58());
+    assert.equal(auctionAccount.highestBid.toNumber(), winningBid.bid);
+  });
+
+});
+
+describe('sealed auction signed reveal', () => {
+
+  let seller;
+  let buyer;
+  let relayer;
+  let mintOwner;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let winningBid;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    buyer = anchor.web3.Keypair.generate();
+    relayer = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(10));
+    await airdrop(program, buyer.publicKey, lamports(10));
+    await airdrop(program, mintOwner.publicKey, lamports(10));
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], 1);
+
+    let auctionTitle = "signed reveal test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let revealTime = Math.floor(Date.now() / 1000) + 10;
+
+    [auctionAddress, bump, auctionAta] = await deriveSealedAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    true,
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(1),
+                                    sealedAuctionOptions(), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+  });
+
+  it('make sealed bid', async () => {
+    winningBid = { bid: lamports(2), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(3) };
+
+    await program.rpc.makeSealedBid(bidHashV2(auctionAddress, buyer.publicKey, winningBid.bid, winningBid.salt),
+                                    new anchor.BN(winningBid.deposit),
+                                    Buffer.from([]), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [buyer]
+    });
+  });
+
+  delay(auctionEndDelay, "delay for reveal period to start");
+
+  it('relayer needs the bidder\'s signature', async () => {
+    let accounts = {
+      auction: auctionAddress,
+      bidder: buyer.publicKey,
+      relayer: relayer.publicKey,
+      instructionsSysvar: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    await assert.rejects(program.rpc.revealSealedBidSigned(new anchor.BN(winningBid.bid),
+                                                           Array.from(winningBid.salt), {
+      accounts: accounts,
+      signers: [relayer]
+    }));
+
+    // a valid signature from the wrong key
+    await assert.rejects(program.rpc.revealSealedBidSigned(new anchor.BN(winningBid.bid),
+                                                           Array.from(winningBid.salt), {
+      accounts: accounts,
+      instructions: [
+        web3.Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: relayer.secretKey,
+          message: revealMessage(auctionAddress, winningBid.bid, winningBid.salt),
+        }),
+      ],
+      signers: [relayer]
+    }));
+  });
+
+  it('relayer reveals with the bidder\'s signature', async () => {
+    await program.rpc.revealSealedBidSigned(new anchor.BN(winningBid.bid),
+                                            Array.from(winningBid.salt), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: buyer.publicKey,
+        relayer: relayer.publicKey,
+        instructionsSysvar: web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      instructions: [
+        web3.Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: buyer.secretKey,
+          message: revealMessage(auctionAddress, winningBid.bid, winningBid.salt),
+        }),
+      ],
+      signers: [relayer]
+    });
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.highestBidder.toBase58(), buyer.publicKey.toBase58());
+    assert.equal(auctionAccount.highestBid.toNumber(), winningBid.bid);
+  });
+
+});
+
+describe('sealed auction reveal book', () => {
+
+  let seller;
+  let first;
+  let second;
+  let third;
+  let mintOwner;
+  let mint;
+  let sellerAta;
+  let firstAtaAddress;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let bids;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    first = anchor.web3.Keypair.generate();
+    second = anchor.web3.Keypair.generate();
+    third = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(10));
+    await airdrop(program, first.publicKey, lamports(10));
+    await airdrop(program, second.publicKey, lamports(10));
+    await airdrop(program, third.publicKey, lamports(10));
+    await airdrop(program, mintOwner.publicKey, lamports(10));
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], 1);
+
+    let auctionTitle = "reveal book test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 5;
+    let revealTime = Math.floor(Date.now() / 1000) + 10;
+
+    [auctionAddress, bump, auctionAta] = await deriveSealedAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+    firstAtaAddress = await serumAta.getAssociatedTokenAddress(first.publicKey, mint.publicKey);
+
+    // second price
+    await program.rpc.createSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    false,
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(3),
+                                    new anchor.BN(1),
+                                    sealedAuctionOptions(), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+  });
+
+  it('make sealed bids', async () => {
+    bids = [
+      { bidder: first, bid: lamports(3), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(4) },
+      { bidder: second, bid: lamports(2), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(3) },
+      { bidder: third, bid: lamports(1), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2) },
+    ];
+
+    for (const bid of bids) {
+      await program.rpc.makeSealedBid(bidHashV2(auctionAddress, bid.bidder.publicKey, bid.bid, bid.salt),
+                                      new anchor.BN(bid.deposit),
+                                      Buffer.from([]), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bid.bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bid.bidder]
+      });
+    }
+  });
+
+  delay(auctionEndDelay, "delay for reveal period to start");
+
+  it('reveals are ranked in the book', async () => {
+    // the runner-up reveals below the leader, it still has to set the second price
+    for (const bid of [bids[0], bids[2], bids[1]]) {
+      await program.rpc.revealSealedBidV2(new anchor.BN(bid.bid),
+                                          Array.from(bid.salt), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bid.bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bid.bidder]
+      });
+    }
+
+    // a bid can't be revealed twice
+    await assert.rejects(program.rpc.revealSealedBidV2(new anchor.BN(bids[0].bid),
+                                                       Array.from(bids[0].salt), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: first.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [first]
+    }));
+  });
+
+  delay(revealPeriodEndDelay, "delay for reveal period to end");
+
+  it('observers can read the ranking', async () => {
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.deepEqual(auctionAccount.revealBook.map(entry => entry.bidder.toBase58()),
+                     bids.map(bid => bid.bidder.publicKey.toBase58()));
+    assert.deepEqual(auctionAccount.revealBook.map(entry => entry.bid.toNumber()),
+                     bids.map(bid => bid.bid));
+    assert.ok(auctionAccount.revealBook.every(entry => entry.revealTime.toNumber() > 0));
+  });
+
+  it('winner pays the second highest bid', async () => {
+    let sellerBalance = await getLamportBalance(program, seller.publicKey);
+
+    await program.rpc.withdrawWinningBidSealed({
+      accounts: {
+        auction: auctionAddress,
+        owner: seller.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [seller]
+    });
+
+    amt = await getLamportBalance(program, seller.publicKey);
+    assert.equal(amt - sellerBalance, bids[1].bid);
+
+    await program.rpc.withdrawItemSealed({
+      accounts: {
+        auction: auctionAddress,
+        auctionAta: auctionAta,
+        highestBidder: first.publicKey,
+        highestBidderAta: firstAtaAddress,
+        mint: mint.publicKey,
+        tokenProgram: splToken.TOKEN_PROGRAM_ID,
+        ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+      },
+      signers: [first]
+    });
+
+    amt = await getTokenBalance(program, firstAtaAddress);
+    assert.equal(amt.amount, 1);
+  });
+
+});
+
+
+describe('sealed auction tie-break', () => {
+
+  let seller;
+  let early;
+  let late;
+  let mintOwner;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let earlyBid;
+  let lateBid;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    early = anchor.web3.Keypair.generate();
+    late = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(10));
+    await airdrop(program, early.publicKey, lamports(10));
+    await airdrop(program, late.publicKey, lamports(10));
+    await airdrop(program, mintOwner.publicKey, lamports(10));
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], 1);
+
+    let auctionTitle = "tie-break test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 8;
+    let revealTime = Math.floor(Date.now() / 1000) + 16;
+
+    [auctionAddress, bump, auctionAta] = await deriveSealedAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    true,
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(1),
+                                    sealedAuctionOptions({ tieBreak: { earliestCommit: {} } }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+  });
+
+  it('first commit', async () => {
+    earlyBid = { bid: lamports(2), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2.5) };
+
+    await program.rpc.makeSealedBid(bidHashV2(auctionAddress, early.publicKey, earlyBid.bid, earlyBid.salt),
+                                    new anchor.BN(earlyBid.deposit),
+                                    Buffer.from([]), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: early.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [early]
+    });
+  });
+
+  delay(2000, "delay so the commits land in different seconds");
+
+  it('second commit', async () => {
+    lateBid = { bid: lamports(2), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2.5) };
+
+    await program.rpc.makeSealedBid(bidHashV2(auctionAddress, late.publicKey, lateBid.bid, lateBid.salt),
+                                    new anchor.BN(lateBid.deposit),
+                                    Buffer.from([]), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: late.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [late]
+    });
+  });
+
+  delay(auctionEndDelay, "delay for reveal period to start");
+
+  it('earlier commit wins an equal bid revealed later', async () => {
+    for (const [bid, bidder] of [[lateBid, late], [earlyBid, early]]) {
+      await program.rpc.revealSealedBidV2(new anchor.BN(bid.bid),
+                                          Array.from(bid.salt), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.highestBidder.toBase58(), early.publicKey.toBase58());
+    assert.deepEqual(auctionAccount.revealBook.map(entry => entry.bidder.toBase58()),
+                     [early.publicKey.toBase58(), late.publicKey.toBase58()]);
+    assert.ok(auctionAccount.revealBook[0].commitTime.toNumber() < auctionAccount.revealBook[1].commitTime.toNumber());
+
+    // the outranked bidder's deposit is free again
+    let lateBalance = await getLamportBalance(program, late.publicKey);
+
+    await program.rpc.reclaimSealedBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: late.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [late]
+    });
+
+    amt = await getLamportBalance(program, late.publicKey);
+    assert.equal(amt - lateBalance, lateBid.deposit);
+  });
+
+});
+
+describe('sealed auction random tie-break', () => {
+
+  let seller;
+  let bidder1;
+  let bidder2;
+  let mintOwner;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+  let auctionAccount;
+  let bids;
+
+  let amt;
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    bidder1 = anchor.web3.Keypair.generate();
+    bidder2 = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(10));
+    await airdrop(program, bidder1.publicKey, lamports(10));
+    await airdrop(program, bidder2.publicKey, lamports(10));
+    await airdrop(program, mintOwner.publicKey, lamports(10));
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], 1);
+
+    let auctionTitle = "random tie-break test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 6;
+    let revealTime = Math.floor(Date.now() / 1000) + 12;
+
+    [auctionAddress, bump, auctionAta] = await deriveSealedAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createSealedAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    true,
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(revealTime),
+                                    new anchor.BN(2),
+                                    new anchor.BN(1),
+                                    sealedAuctionOptions({ tieBreak: { random: {} } }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+  });
+
+  it('commit equal bids', async () => {
+    bids = [];
+    for (const bidder of [bidder1, bidder2]) {
+      let bid = { bidder: bidder, bid: lamports(2), salt: web3.Keypair.generate().publicKey.toBytes(), deposit: lamports(2.5) };
+      bids.push(bid);
+
+      await program.rpc.makeSealedBid(bidHashV2(auctionAddress, bidder.publicKey, bid.bid, bid.salt),
+                                      new anchor.BN(bid.deposit),
+                                      Buffer.from([]), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bidder]
+      });
+    }
+  });
+
+  delay(auctionEndDelay, "delay for reveal period to start");
+
+  it('tied bids stay locked until the draw', async () => {
+    for (const bid of bids) {
+      await program.rpc.revealSealedBidV2(new anchor.BN(bid.bid),
+                                          Array.from(bid.salt), {
+        accounts: {
+          auction: auctionAddress,
+          bidder: bid.bidder.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        },
+        signers: [bid.bidder]
+      });
+    }
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.revealBook.length, 2);
+    assert.equal(auctionAccount.tiesDrawn, false);
+
+    await assert.rejects(program.rpc.reclaimSealedBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder2.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder2]
+    }));
+
+    // the draw slot can only be committed once reveals are over
+    await assert.rejects(program.rpc.commitTieBreakDrawSealed({
+      accounts: {
+        auction: auctionAddress,
+        slotHashes: web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      },
+    }));
+  });
+
+  delay(revealPeriodEndDelay, "delay for reveal period to end");
+
+  it('commit tie-break draw slot', async () => {
+    await program.rpc.commitTieBreakDrawSealed({
+      accounts: {
+        auction: auctionAddress,
+        slotHashes: web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      },
+    });
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.ok(auctionAccount.tieDrawSlot.toNumber() > 0);
+  });
+
+  delay(auctionEndDelay, "delay for the draw slot to pass");
+
+  it('draw tie-break', async () => {
+    await program.rpc.drawTieBreakSealed({
+      accounts: {
+        auction: auctionAddress,
+        slotHashes: web3.SYSVAR_SLOT_HASHES_PUBKEY,
+      },
+    });
+
+    auctionAccount = await program.account.sealedAuction.fetch(auctionAddress);
+    assert.equal(auctionAccount.tiesDrawn, true);
+    assert.equal(auctionAccount.highestBidder.toBase58(), auctionAccount.revealBook[0].bidder.toBase58());
+    assert.ok([bidder1.publicKey.toBase58(), bidder2.publicKey.toBase58()].includes(auctionAccount.highestBidder.toBase58()));
+  });
+
+  it('losing tied bidder reclaims', async () => {
+    let loser = bids.find(bid => !bid.bidder.publicKey.equals(auctionAccount.highestBidder));
+    let loserBalance = await getLamportBalance(program, loser.bidder.publicKey);
+
+    await program.rpc.reclaimSealedBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: loser.bidder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [loser.bidder]
+    });
+
+    amt = await getLamportBalance(program, loser.bidder.publicKey);
+    assert.equal(amt - loserBalance, loser.deposit);
+  });
+
+});
+
+describe('open auction tie-break', () => {
+
+  let seller;
+  let bidder1;
+  let bidder2;
+  let bidder3;
+  let mintOwner;
+  let mint;
+  let sellerAta;
+  let auctionAddress;
+  let bump;
+  let auctionAta;
+
+  let amt;
+
+  function makeBid(bidder, bid: number) {
+    return program.rpc.makeOpenBid(new anchor.BN(lamports(bid)), {
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder]
+    });
+  }
+
+  it('init auction', async () => {
+    seller = anchor.web3.Keypair.generate();
+    bidder1 = anchor.web3.Keypair.generate();
+    bidder2 = anchor.web3.Keypair.generate();
+    bidder3 = anchor.web3.Keypair.generate();
+    mintOwner = anchor.web3.Keypair.generate();
+
+    await airdrop(program, seller.publicKey, lamports(5));
+    await airdrop(program, bidder1.publicKey, lamports(5));
+    await airdrop(program, bidder2.publicKey, lamports(5));
+    await airdrop(program, bidder3.publicKey, lamports(5));
+    await airdrop(program, mintOwner.publicKey, lamports(5));
+
+    mint = await splToken.Token.createMint(
+      program.provider.connection,
+      mintOwner,
+      mintOwner.publicKey,
+      null,
+      0,
+      splToken.TOKEN_PROGRAM_ID,
+    );
+
+    sellerAta = await mint.getOrCreateAssociatedAccountInfo(seller.publicKey);
+    await mint.mintTo(sellerAta.address, mintOwner.publicKey, [], 2);
+
+    let auctionTitle = "open tie-break test";
+    let startTime = Math.floor(Date.now() / 1000) - 60;
+    let endTime = Math.floor(Date.now() / 1000) + 20;
+
+    [auctionAddress, bump, auctionAta] = await deriveOpenAuction(program, seller.publicKey, mint.publicKey, auctionTitle);
+
+    await program.rpc.createOpenAuction(new anchor.BN(bump),
+                                    auctionTitle,
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(lamports(0.1)),
+                                    new anchor.BN(startTime),
+                                    new anchor.BN(endTime),
+                                    new anchor.BN(3),
+                                    new anchor.BN(2),
+                                    openAuctionOptions({ units: new anchor.BN(2) }), {
+        accounts: {
+          auction: auctionAddress,
+          auctionAta: auctionAta,
+          owner: seller.publicKey,
+          ownerAta: sellerAta.address,
+          mint: mint.publicKey,
+          tokenProgram: splToken.TOKEN_PROGRAM_ID,
+          ataProgram: serumAta.ASSOCIATED_TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rentSysvar: web3.SYSVAR_RENT_PUBKEY,
+        },
+        signers: [seller],
+    });
+  });
+
+  it('first bidder joins low', async () => {
+    await makeBid(bidder1, 0.5);
+  });
+
+  delay(2000, "delay so the bids land in different seconds");
+
+  it('second bidder reaches the tied amount first', async () => {
+    await makeBid(bidder2, 1);
+  });
+
+  delay(2000, "delay so the bids land in different seconds");
+
+  it('earliest to reach a tied bid keeps the last unit', async () => {
+    // bidder1 joined first but only matches bidder2 now
+    await makeBid(bidder1, 0.5);
+    await makeBid(bidder3, 2);
+
+    let bidderBalance = await getLamportBalance(program, bidder1.publicKey);
+
+    await program.rpc.reclaimOpenBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder1.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder1]
+    });
+
+    amt = await getLamportBalance(program, bidder1.publicKey);
+    assert.equal(amt - bidderBalance, lamports(1));
+
+    await assert.rejects(program.rpc.reclaimOpenBid({
+      accounts: {
+        auction: auctionAddress,
+        bidder: bidder2.publicKey,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      },
+      signers: [bidder2]
+    }));
+  });
+
 });
\ No newline at end of file